@@ -0,0 +1,5 @@
+-- Registra quando o post foi para a lixeira, usado para o expurgo automático
+ALTER TABLE posts
+    ADD COLUMN deleted_at DATETIME NULL DEFAULT NULL;
+
+UPDATE posts SET deleted_at = updated_at WHERE is_active = 0;
@@ -0,0 +1,8 @@
+-- Ciclo de vida dos posts: rascunho, agendado, publicado e arquivado
+ALTER TABLE posts
+    ADD COLUMN status ENUM('draft', 'scheduled', 'published', 'archived') NOT NULL DEFAULT 'published',
+    MODIFY COLUMN publication_date DATETIME NULL DEFAULT NULL;
+
+UPDATE posts SET publication_date = created_at WHERE publication_date IS NULL;
+
+CREATE INDEX idx_posts_status_publication ON posts (status, publication_date);
@@ -0,0 +1,18 @@
+-- Histórico completo de cada versão salva de um post
+CREATE TABLE post_revisions (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    post_id INT NOT NULL,
+    user_id INT NULL,
+    author_id INT NOT NULL,
+    category_id INT NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    post_image_url VARCHAR(255) NULL,
+    content LONGTEXT NOT NULL,
+    slug VARCHAR(255) NOT NULL,
+    status ENUM('draft', 'scheduled', 'published', 'archived') NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_post_revisions_post (post_id),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+);
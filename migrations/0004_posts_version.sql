@@ -0,0 +1,3 @@
+-- Contador usado no controle de concorrência otimista das edições
+ALTER TABLE posts
+    ADD COLUMN version INT NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Nomes duplicados são rejeitados com 409 pela API; o índice garante o mesmo no banco
+ALTER TABLE authors ADD UNIQUE KEY uq_authors_name (name);
+ALTER TABLE categories ADD UNIQUE KEY uq_categories_name (name);
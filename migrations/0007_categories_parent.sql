@@ -0,0 +1,5 @@
+-- Categorias hierárquicas: cada categoria pode ter uma categoria pai
+ALTER TABLE categories
+    ADD COLUMN parent_id INT NULL DEFAULT NULL,
+    ADD CONSTRAINT fk_categories_parent FOREIGN KEY (parent_id) REFERENCES categories(id),
+    ADD INDEX idx_categories_parent (parent_id);
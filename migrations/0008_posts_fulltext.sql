@@ -0,0 +1,4 @@
+-- Índices FULLTEXT usados pela busca; o índice só de título permite pesar mais essas ocorrências
+ALTER TABLE posts
+    ADD FULLTEXT INDEX ft_posts_title (title),
+    ADD FULLTEXT INDEX ft_posts_search (title, description, content);
@@ -0,0 +1,3 @@
+-- HTML renderizado a partir do Markdown, regravado a cada edição do post
+ALTER TABLE posts
+    ADD COLUMN content_html LONGTEXT NULL DEFAULT NULL;
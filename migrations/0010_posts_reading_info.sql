@@ -0,0 +1,5 @@
+-- Sumário (JSON), contagem de palavras e tempo de leitura calculados ao salvar o post
+ALTER TABLE posts
+    ADD COLUMN toc LONGTEXT NULL DEFAULT NULL,
+    ADD COLUMN word_count INT NULL DEFAULT NULL,
+    ADD COLUMN reading_time_minutes INT NULL DEFAULT NULL;
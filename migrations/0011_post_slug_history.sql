@@ -0,0 +1,10 @@
+-- Slugs antigos de cada post, usados para redirecionar links externos
+CREATE TABLE post_slug_history (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    post_id INT NOT NULL,
+    slug VARCHAR(255) NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE KEY uq_post_slug_history_slug (slug),
+    INDEX idx_post_slug_history_post (post_id),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
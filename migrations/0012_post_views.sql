@@ -0,0 +1,9 @@
+-- Visualizações agregadas por post e por dia
+CREATE TABLE post_views (
+    post_id INT NOT NULL,
+    viewed_on DATE NOT NULL,
+    views INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (post_id, viewed_on),
+    INDEX idx_post_views_day (viewed_on),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
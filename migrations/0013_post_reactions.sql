@@ -0,0 +1,11 @@
+-- Reações dos leitores; cada usuário marca cada tipo no máximo uma vez por post
+CREATE TABLE post_reactions (
+    post_id INT NOT NULL,
+    user_id INT NOT NULL,
+    reaction ENUM('like', 'love', 'laugh', 'insightful', 'celebrate') NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (post_id, reaction, user_id),
+    INDEX idx_post_reactions_user (user_id, post_id),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Marca de edição dos comentários, exibida como "editado" nos payloads
+ALTER TABLE comments
+    ADD COLUMN edited_at DATETIME NULL DEFAULT NULL;
@@ -0,0 +1,7 @@
+-- Fila de moderação; comentários já existentes continuam publicados
+ALTER TABLE comments
+    ADD COLUMN status ENUM('pending', 'approved', 'rejected', 'spam') NOT NULL DEFAULT 'approved',
+    ADD COLUMN moderated_at DATETIME NULL DEFAULT NULL,
+    ADD COLUMN moderated_by INT NULL DEFAULT NULL,
+    ADD INDEX idx_comments_status (status, created_at),
+    ADD INDEX idx_comments_user_status (user_id, status);
@@ -0,0 +1,21 @@
+-- Pontuação de spam de cada comentário e o rótulo usado no treino do filtro bayesiano
+ALTER TABLE comments
+    ADD COLUMN spam_score DOUBLE NULL DEFAULT NULL,
+    ADD COLUMN spam_reasons TEXT NULL DEFAULT NULL,
+    ADD COLUMN trained_as ENUM('spam', 'ham') NULL DEFAULT NULL,
+    ADD INDEX idx_comments_user_created (user_id, created_at);
+
+-- Frequência de cada palavra em comentários marcados como spam ou aprovados
+CREATE TABLE comment_spam_tokens (
+    token VARCHAR(64) NOT NULL PRIMARY KEY,
+    spam INT NOT NULL DEFAULT 0,
+    ham INT NOT NULL DEFAULT 0
+);
+
+-- Quantidade de comentários usados no treino por rótulo
+CREATE TABLE comment_spam_corpus (
+    label ENUM('spam', 'ham') NOT NULL PRIMARY KEY,
+    documents INT NOT NULL DEFAULT 0
+);
+
+INSERT INTO comment_spam_corpus (label, documents) VALUES ('spam', 0), ('ham', 0);
@@ -0,0 +1,14 @@
+-- Reações em comentários, usadas na ordenação por "mais reagidos"
+CREATE TABLE comment_reactions (
+    comment_id INT NOT NULL,
+    user_id INT NOT NULL,
+    reaction ENUM('like', 'love', 'laugh', 'insightful', 'celebrate') NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (comment_id, reaction, user_id),
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Contagem de comentários publicados embutida nos payloads de posts
+ALTER TABLE comments
+    ADD INDEX idx_comments_post_visible (post_id, status, is_deleted);
@@ -0,0 +1,4 @@
+-- Revisões passam a guardar a data de publicação e as tags para o rollback completo
+ALTER TABLE post_revisions
+    ADD COLUMN publication_date DATETIME NULL AFTER status,
+    ADD COLUMN tag_names TEXT NULL AFTER publication_date;
@@ -2,7 +2,7 @@ use dotenv::dotenv;
 use once_cell::sync::OnceCell;
 use sqlx::{
     mysql::MySqlConnectOptions,
-    mysql::{MySqlPool, MySqlRow},
+    mysql::{MySqlPool, MySqlQueryResult, MySqlRow},
     MySql, Pool,
 };
 use std::env;
@@ -81,6 +81,25 @@ impl HelperMySql {
         Ok(rows)
     }
 
+    pub async fn execute_with_params<'a, T>(
+        query: &'a str,
+        params: Vec<T>,
+    ) -> Result<MySqlQueryResult, sqlx::Error>
+    where
+        T: sqlx::Encode<'a, MySql> + sqlx::Type<MySql> + Send + Sync + 'a,
+    {
+        let instance = Self::get_instance().expect("Database not initialized");
+
+        let mut query_builder = sqlx::query(query);
+
+        for param in params {
+            query_builder = query_builder.bind(param);
+        }
+
+        // Diferente do fetch_all, devolve linhas afetadas e o último id inserido
+        query_builder.execute(&instance.pool).await
+    }
+
     pub async fn query<T>(query: &str) -> Result<Vec<T>, sqlx::Error>
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
@@ -1,6 +1,7 @@
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    extract::OriginalUri,
+    http::{HeaderMap, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
@@ -36,6 +37,12 @@ pub struct HelperMiddlewareToken {
     decoding_key: DecodingKey,
 }
 
+impl Default for HelperMiddlewareToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HelperMiddlewareToken {
     pub fn new() -> Self {
         dotenv().ok();
@@ -54,7 +61,7 @@ impl HelperMiddlewareToken {
         let params = vec![user.user.email.clone()];
         let role = match HelperMySql::execute_query_with_params(query, params).await {
             Ok(rows) => rows
-                .get(0)
+                .first()
                 .and_then(|row| row.try_get("role").ok())
                 .unwrap_or("user".to_string()),
             Err(_) => "user".to_string(),
@@ -105,42 +112,80 @@ impl HelperMiddlewareToken {
             )),
         }
     }
-    pub async fn verify_token(&self, mut req: Request<Body>, next: Next) -> Response {
-        let auth_header = req
+
+    // Rotas públicas aceitam token opcional para personalizar a resposta
+    pub fn claims_from_headers(&self, headers: &HeaderMap) -> Option<Claims> {
+        let token = headers
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(|s| s.trim_start_matches("Bearer "))?;
+
+        decode::<Claims>(token, &self.decoding_key, &Validation::default())
+            .ok()
+            .map(|token_data| token_data.claims)
+    }
+
+    fn request_claims(&self, req: &Request<Body>) -> Result<Claims, (StatusCode, &'static str)> {
+        let token = req
             .headers()
             .get("Authorization")
             .and_then(|value| value.to_str().ok())
-            .map(|s| s.trim_start_matches("Bearer "));
-
-        match auth_header {
-            Some(token) => {
-                let validation = Validation::default();
-                match decode::<Claims>(token, &self.decoding_key, &validation) {
-                    Ok(token_data) => {
-                        if req.uri().path().starts_with("/post")
-                            && token_data.claims.role != "admin"
-                        {
-                            return (
-                                StatusCode::FORBIDDEN,
-                                Json(json!({ "message": "Acesso negado: apenas administradores"})),
-                            )
-                                .into_response();
-                        }
-                        req.extensions_mut().insert(token_data.claims);
-                        next.run(req).await
-                    }
-                    Err(_) => (
-                        StatusCode::UNAUTHORIZED,
-                        Json(json!({ "message": "Token inválido" })),
+            .map(|s| s.trim_start_matches("Bearer "))
+            .ok_or((StatusCode::UNAUTHORIZED, "Token não fornecido"))?;
+
+        decode::<Claims>(token, &self.decoding_key, &Validation::default())
+            .map(|token_data| token_data.claims)
+            .map_err(|_| (StatusCode::UNAUTHORIZED, "Token inválido"))
+    }
+
+    pub async fn verify_token(&self, mut req: Request<Body>, next: Next) -> Response {
+        match self.request_claims(&req) {
+            Ok(claims) => {
+                // Dentro do nest o caminho chega sem o prefixo; o OriginalUri guarda o caminho completo
+                let path = req
+                    .extensions()
+                    .get::<OriginalUri>()
+                    .map(|uri| uri.path())
+                    .unwrap_or(req.uri().path());
+
+                if path.starts_with("/post") && claims.role != "admin" {
+                    return (
+                        StatusCode::FORBIDDEN,
+                        Json(json!({ "message": "Acesso negado: apenas administradores"})),
                     )
-                        .into_response(),
+                        .into_response();
                 }
+                req.extensions_mut().insert(claims);
+                next.run(req).await
             }
-            None => (
-                StatusCode::UNAUTHORIZED,
-                Json(json!({ "message": "Token não fornecido" })),
+            Err((status, message)) => (status, Json(json!({ "message": message }))).into_response(),
+        }
+    }
+
+    // Exige papel de administrador em qualquer caminho, para rotas fora de /post
+    pub async fn require_admin(&self, mut req: Request<Body>, next: Next) -> Response {
+        match self.request_claims(&req) {
+            Ok(claims) if claims.role != "admin" => (
+                StatusCode::FORBIDDEN,
+                Json(json!({ "message": "Acesso negado: apenas administradores"})),
             )
                 .into_response(),
+            Ok(claims) => {
+                req.extensions_mut().insert(claims);
+                next.run(req).await
+            }
+            Err((status, message)) => (status, Json(json!({ "message": message }))).into_response(),
+        }
+    }
+
+    // Só exige um token válido, sem restringir o papel do usuário
+    pub async fn authenticate(&self, mut req: Request<Body>, next: Next) -> Response {
+        match self.request_claims(&req) {
+            Ok(claims) => {
+                req.extensions_mut().insert(claims);
+                next.run(req).await
+            }
+            Err((status, message)) => (status, Json(json!({ "message": message }))).into_response(),
         }
     }
 }
@@ -32,6 +32,18 @@ impl HelpersResponse {
             .into_response()
     }
 
+    pub fn conflict(message: &str) -> Response {
+        (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "code": "CONFLICT",
+                "type": "error",
+                "message": message
+            })),
+        )
+            .into_response()
+    }
+
     // Função para resposta de erro com detalhes adicionais
     pub fn error_with_detail<E: std::fmt::Display>(message: &str, err: E) -> Response {
         (
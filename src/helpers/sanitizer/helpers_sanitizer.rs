@@ -0,0 +1,82 @@
+use ammonia::Builder;
+use dotenv::dotenv;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::env;
+
+pub struct HelpersSanitizer;
+
+#[derive(Debug, Clone, Copy)]
+pub enum SanitizePolicy {
+    Post,
+    Comment,
+}
+
+static POST_SANITIZER: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = Builder::default();
+    builder
+        .add_generic_attributes(["class", "id"])
+        .add_tag_attributes("td", ["style"])
+        .add_tag_attributes("th", ["style"]);
+
+    configure(&mut builder, "SANITIZER_POST");
+    builder
+});
+
+// Comentários aceitam só formatação inline, e links nunca repassam reputação
+static COMMENT_SANITIZER: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = Builder::empty();
+    builder
+        .tags(HashSet::from(["a", "b", "strong", "i", "em", "code", "br"]))
+        .tag_attributes(HashMap::from([("a", HashSet::from(["href", "title"]))]))
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(Some("nofollow ugc"));
+
+    configure(&mut builder, "SANITIZER_COMMENT");
+    builder
+});
+
+// Os valores vêm do ambiente uma única vez, então podem viver pelo resto do processo
+fn env_static(name: &str) -> Option<&'static str> {
+    env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| &*Box::leak(value.into_boxed_str()))
+}
+
+/// Aplica `<PREFIXO>_TAGS` ("p,a,em") e `<PREFIXO>_ATTRIBUTES` ("a:href|title,img:src|alt").
+fn configure(builder: &mut Builder<'static>, prefix: &str) {
+    dotenv().ok();
+
+    if let Some(tags) = env_static(&format!("{}_TAGS", prefix)) {
+        builder.tags(
+            tags.split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+        );
+    }
+
+    if let Some(attributes) = env_static(&format!("{}_ATTRIBUTES", prefix)) {
+        let mut tag_attributes: HashMap<&'static str, HashSet<&'static str>> = HashMap::new();
+        for entry in attributes.split(',') {
+            if let Some((tag, attrs)) = entry.split_once(':') {
+                tag_attributes
+                    .entry(tag.trim())
+                    .or_default()
+                    .extend(attrs.split('|').map(str::trim).filter(|a| !a.is_empty()));
+            }
+        }
+        builder.tag_attributes(tag_attributes);
+    }
+}
+
+impl HelpersSanitizer {
+    pub fn clean(html: &str, policy: SanitizePolicy) -> String {
+        let builder = match policy {
+            SanitizePolicy::Post => &*POST_SANITIZER,
+            SanitizePolicy::Comment => &*COMMENT_SANITIZER,
+        };
+        builder.clean(html).to_string()
+    }
+}
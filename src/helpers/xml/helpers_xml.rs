@@ -0,0 +1,12 @@
+pub struct HelpersXml;
+
+impl HelpersXml {
+    pub fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+}
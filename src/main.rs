@@ -10,6 +10,12 @@ pub mod helpers {
     pub mod response {
         pub mod helpers_response;
     }
+    pub mod sanitizer {
+        pub mod helpers_sanitizer;
+    }
+    pub mod xml {
+        pub mod helpers_xml;
+    }
 }
 
 pub mod mvc {
@@ -39,6 +45,14 @@ pub mod mvc {
         pub mod comment {
             pub mod controller_comment;
         }
+
+        pub mod feed {
+            pub mod controller_feed;
+        }
+
+        pub mod sitemap {
+            pub mod controller_sitemap;
+        }
     }
 
     pub mod routes {
@@ -54,19 +68,74 @@ pub mod mvc {
             pub mod route_comment;
         }
 
+        pub mod feed {
+            pub mod route_feed;
+        }
+
+        pub mod sitemap {
+            pub mod route_sitemap;
+        }
     }
 
     pub mod services {
+        pub mod comment {
+            pub mod spam {
+                pub mod services_comment_spam;
+            }
+        }
+
         pub mod user {
             pub mod email {
                 pub mod services_user_email;
             }
         }
 
+        pub mod post {
+            pub mod feed {
+                pub mod services_post_feed;
+            }
+
+            pub mod markdown {
+                pub mod services_post_markdown;
+            }
+
+            pub mod related {
+                pub mod services_post_related;
+            }
+
+            pub mod scheduler {
+                pub mod services_post_scheduler;
+            }
+
+            pub mod search {
+                pub mod services_post_search;
+            }
+
+            pub mod search_index {
+                pub mod services_post_search_index;
+            }
+
+            pub mod sitemap {
+                pub mod services_post_sitemap;
+            }
+
+            pub mod slug {
+                pub mod services_post_slug;
+            }
+
+            pub mod views {
+                pub mod services_post_views;
+            }
+        }
     }
 }
 
 use crate::helpers::db::helpers_mysql::HelperMySql;
+use crate::mvc::models::post::model_post::ModelPost;
+use crate::mvc::services::post::scheduler::services_post_scheduler::ServicesPostScheduler;
+use crate::mvc::services::post::search_index::services_post_search_index::ServicesPostSearchIndex;
+use crate::mvc::services::post::views::services_post_views::ServicesPostViews;
+use std::net::SocketAddr;
 
 #[tokio::main]
 async fn main() {
@@ -77,13 +146,34 @@ async fn main() {
 
     match HelperMySql::init().await {
         Ok(_helper) => {
-            println!("Conexão estabelecida com sucesso!")
+            println!("Conexão estabelecida com sucesso!");
+            ServicesPostScheduler::spawn();
+            ServicesPostViews::spawn();
+
+            tokio::spawn(async {
+                match ModelPost::backfill_rendered_content().await {
+                    Ok(0) => {}
+                    Ok(rendered) => println!("{} post(s) antigo(s) renderizado(s)", rendered),
+                    Err(e) => eprintln!("Erro ao renderizar posts antigos: {}", e),
+                }
+            });
+
+            match ServicesPostSearchIndex::init() {
+                Ok(_) => ServicesPostSearchIndex::schedule_rebuild(),
+                Err(e) => eprintln!("Erro ao iniciar índice de busca: {}", e),
+            }
         }
         Err(e) => {
             eprintln!("Erro ao conectar ao banco: {}", e)
         }
     };
 
-    axum::serve(listener, app).await.unwrap();
+    // O endereço do cliente é usado para deduplicar visualizações de posts
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
     println!("passou aqui");
 }
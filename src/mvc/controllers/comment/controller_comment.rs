@@ -1,9 +1,13 @@
 use crate::{
     helpers::middleware::token::Claims,
-    mvc::models::comment::model_comment::{CommentRequest, ModelComment},
+    mvc::models::comment::model_comment::{
+        CommentListQuery, CommentRequest, CommentStatus, EditCommentRequest, ModelComment,
+        ModerateComments, ModerationQueueQuery,
+    },
+    mvc::models::post::model_post::ReactionRequest,
 };
 use axum::{
-    extract::{Extension, Json, Path},
+    extract::{Extension, Json, Path, Query},
     http::StatusCode,
     response::IntoResponse,
 };
@@ -12,8 +16,11 @@ use serde_json::json;
 pub struct ControllerComment;
 
 impl ControllerComment {
-    pub async fn get_all_comments_by_post(Path(post_id): Path<i32>) -> impl IntoResponse {
-        match ModelComment::select_comment_by_post(post_id).await {
+    pub async fn get_all_comments_by_post(
+        Path(post_id): Path<i32>,
+        Query(filters): Query<CommentListQuery>,
+    ) -> impl IntoResponse {
+        match ModelComment::select_comment_by_post(post_id, &filters).await {
             Ok(comments) => (
                 StatusCode::OK,
                 Json(json!({
@@ -30,16 +37,137 @@ impl ControllerComment {
         Extension(claims): Extension<Claims>,
         Json(new_comment): Json<CommentRequest>,
     ) -> impl IntoResponse {
-        match ModelComment::insert_comment(new_comment, claims.user_id).await {
-            Ok(_) => (
+        let is_admin = claims.role == "admin";
+        match ModelComment::insert_comment(new_comment, claims.user_id, is_admin).await {
+            Ok(CommentStatus::Approved) => (
                 StatusCode::CREATED,
                 Json(json!({
                     "status": true,
                     "message": "comentário criado com sucesso",
+                    "moderation_status": CommentStatus::Approved,
+                })),
+            )
+                .into_response(),
+            Ok(status) => (
+                StatusCode::ACCEPTED,
+                Json(json!({
+                    "status": true,
+                    "message": "comentário enviado para moderação",
+                    "moderation_status": status,
+                })),
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn edit_comment(
+        Extension(claims): Extension<Claims>,
+        Path(comment_id): Path<i32>,
+        Json(edit): Json<EditCommentRequest>,
+    ) -> impl IntoResponse {
+        let is_admin = claims.role == "admin";
+        match ModelComment::update_comment(comment_id, edit, claims.user_id, is_admin).await {
+            Ok(comment) if comment["moderation_status"] == CommentStatus::Approved.as_str() => (
+                StatusCode::OK,
+                Json(json!({
+                    "status": true,
+                    "message": "comentário editado com sucesso",
+                    "data": comment,
+                })),
+            )
+                .into_response(),
+            Ok(comment) => (
+                StatusCode::ACCEPTED,
+                Json(json!({
+                    "status": true,
+                    "message": "comentário editado e enviado para moderação",
+                    "data": comment,
+                })),
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn delete_comment(
+        Extension(claims): Extension<Claims>,
+        Path(comment_id): Path<i32>,
+    ) -> impl IntoResponse {
+        let is_admin = claims.role == "admin";
+        match ModelComment::delete_comment(comment_id, claims.user_id, is_admin).await {
+            Ok(_) => (
+                StatusCode::OK,
+                Json(json!({
+                    "status": true,
+                    "message": "comentário removido com sucesso",
                 })),
             )
                 .into_response(),
-            Err(err) => println!("erwsdf {:?}", err).into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn get_moderation_queue(
+        Query(filters): Query<ModerationQueueQuery>,
+    ) -> impl IntoResponse {
+        match ModelComment::select_moderation_queue(&filters).await {
+            Ok((comments, total)) => (
+                StatusCode::OK,
+                Json(json!({
+                    "status": true,
+                    "data": comments,
+                    "pagination": {
+                        "page": filters.page(),
+                        "per_page": filters.per_page(),
+                        "total": total,
+                    },
+                })),
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn moderate_comments(
+        Extension(claims): Extension<Claims>,
+        Json(moderation): Json<ModerateComments>,
+    ) -> impl IntoResponse {
+        match ModelComment::moderate_comments(moderation, claims.user_id).await {
+            Ok(updated) => (
+                StatusCode::OK,
+                Json(json!({
+                    "status": true,
+                    "message": "comentários moderados com sucesso",
+                    "data": { "updated": updated },
+                })),
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn toggle_reaction(
+        Extension(claims): Extension<Claims>,
+        Path(comment_id): Path<i32>,
+        Json(request): Json<ReactionRequest>,
+    ) -> impl IntoResponse {
+        match ModelComment::toggle_reaction(comment_id, claims.user_id, request.reaction).await {
+            Ok((reacted, total)) => (
+                StatusCode::OK,
+                Json(json!({
+                    "status": true,
+                    "message": if reacted { "reação registrada" } else { "reação removida" },
+                    "data": {
+                        "comment_id": comment_id,
+                        "reaction": request.reaction,
+                        "reacted": reacted,
+                        "reaction_count": total,
+                    },
+                })),
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
         }
     }
 }
@@ -0,0 +1,146 @@
+use crate::mvc::models::post::model_post::{ModelPost, PostListQuery};
+use crate::mvc::services::post::feed::services_post_feed::{FeedChannel, ServicesPostFeed};
+use axum::{
+    extract::Path,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+};
+use dotenv::dotenv;
+use std::env;
+
+pub struct ControllerFeed;
+
+#[derive(Clone, Copy)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+fn latest_published(category_id: Option<i32>, author_id: Option<i32>) -> PostListQuery {
+    dotenv().ok();
+    // O feed usa a mesma listagem dos posts, então FEED_ITEMS_LIMIT
+    // acima de MAX_PER_PAGE (100) vale como 100
+    let limit = env::var("FEED_ITEMS_LIMIT")
+        .ok()
+        .and_then(|limit| limit.parse::<u32>().ok())
+        .unwrap_or(20)
+        .min(PostListQuery::MAX_PER_PAGE);
+
+    PostListQuery {
+        per_page: Some(limit),
+        category_id,
+        author_id,
+        sort: Some("publication_date".to_string()),
+        order: Some("desc".to_string()),
+        ..Default::default()
+    }
+}
+
+impl ControllerFeed {
+    async fn render(
+        format: FeedFormat,
+        filters: PostListQuery,
+        subtitle: Option<String>,
+        path: &str,
+    ) -> Response {
+        dotenv().ok();
+        let site_url = env::var("BASE_URL").expect("BASE_URL não configurada");
+        let api_url = env::var("API_URL").unwrap_or_else(|_| site_url.clone());
+        let blog_title = env::var("BLOG_TITLE").unwrap_or_else(|_| "Blog".to_string());
+        let blog_description = env::var("BLOG_DESCRIPTION").unwrap_or_default();
+
+        let posts = match ModelPost::select_post(&filters, true).await {
+            Ok(page) => page.posts,
+            Err(err) => return err.into_response(),
+        };
+
+        let channel = FeedChannel {
+            title: match subtitle {
+                Some(subtitle) => format!("{} - {}", blog_title, subtitle),
+                None => blog_title,
+            },
+            description: blog_description,
+            feed_url: format!("{}{}", api_url.trim_end_matches('/'), path),
+            site_url,
+        };
+
+        let (content_type, body) = match format {
+            FeedFormat::Rss => (
+                "application/rss+xml; charset=utf-8",
+                ServicesPostFeed::rss(&channel, &posts),
+            ),
+            FeedFormat::Atom => (
+                "application/atom+xml; charset=utf-8",
+                ServicesPostFeed::atom(&channel, &posts),
+            ),
+        };
+
+        (StatusCode::OK, [(CONTENT_TYPE, content_type)], body).into_response()
+    }
+
+    async fn render_category(format: FeedFormat, category_id: i32, path: String) -> Response {
+        let category = match ModelPost::select_category_by_id(category_id).await {
+            Ok(category) => category,
+            Err(err) => return err.into_response(),
+        };
+        let name = category["name"].as_str().map(str::to_string);
+
+        Self::render(
+            format,
+            latest_published(Some(category_id), None),
+            name,
+            &path,
+        )
+        .await
+    }
+
+    async fn render_author(format: FeedFormat, author_id: i32, path: String) -> Response {
+        let author = match ModelPost::select_author_by_id(author_id).await {
+            Ok(author) => author,
+            Err(err) => return err.into_response(),
+        };
+        let name = author["name"].as_str().map(str::to_string);
+
+        Self::render(format, latest_published(None, Some(author_id)), name, &path).await
+    }
+
+    pub async fn rss() -> impl IntoResponse {
+        Self::render(
+            FeedFormat::Rss,
+            latest_published(None, None),
+            None,
+            "/feed.xml",
+        )
+        .await
+    }
+
+    pub async fn atom() -> impl IntoResponse {
+        Self::render(
+            FeedFormat::Atom,
+            latest_published(None, None),
+            None,
+            "/atom.xml",
+        )
+        .await
+    }
+
+    pub async fn rss_by_category(Path(category_id): Path<i32>) -> impl IntoResponse {
+        let path = format!("/feed/category/{}", category_id);
+        Self::render_category(FeedFormat::Rss, category_id, path).await
+    }
+
+    pub async fn atom_by_category(Path(category_id): Path<i32>) -> impl IntoResponse {
+        let path = format!("/atom/category/{}", category_id);
+        Self::render_category(FeedFormat::Atom, category_id, path).await
+    }
+
+    pub async fn rss_by_author(Path(author_id): Path<i32>) -> impl IntoResponse {
+        let path = format!("/feed/author/{}", author_id);
+        Self::render_author(FeedFormat::Rss, author_id, path).await
+    }
+
+    pub async fn atom_by_author(Path(author_id): Path<i32>) -> impl IntoResponse {
+        let path = format!("/atom/author/{}", author_id);
+        Self::render_author(FeedFormat::Atom, author_id, path).await
+    }
+}
@@ -1,52 +1,384 @@
+use crate::helpers::middleware::token::{Claims, HelperMiddlewareToken};
+use crate::helpers::response::helpers_response::HelpersResponse;
 use crate::mvc::models::post::model_post::{
-    CreateAuthor, CreateCategory, DeletePost, EditPost, ModelPost, PostRequest,
+    CreateAuthor, CreateCategory, CreateTag, DeleteAuthor, DeleteCategory, DeletePost, DeleteTag,
+    EditAuthor, EditCategory, EditPost, EditTag, ModelPost, PopularPostsQuery,
+    PostIndexSearchQuery, PostListQuery, PostRequest, PostSearchQuery, PostStatus, ReactionRequest,
+    RelatedPostsQuery, RevisionDiffQuery,
 };
-use axum::{extract::Json, extract::Path, http::StatusCode, response::IntoResponse};
+use crate::mvc::services::post::related::services_post_related::ServicesPostRelated;
+use crate::mvc::services::post::search::services_post_search::{
+    ServicesPostSearch, MIN_TERM_LENGTH,
+};
+use crate::mvc::services::post::search_index::services_post_search_index::{
+    SearchIndexQuery, ServicesPostSearchIndex,
+};
+use crate::mvc::services::post::slug::services_post_slug::ServicesPostSlug;
+use crate::mvc::services::post::views::services_post_views::ServicesPostViews;
+use axum::{
+    extract::{ConnectInfo, Extension, Json, Path, Query},
+    http::{
+        header::{AUTHORIZATION, ETAG, IF_MATCH, LOCATION, USER_AGENT},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDateTime;
+use dotenv::dotenv;
 use serde_json::{json, Value};
+use std::env;
+use std::net::{IpAddr, SocketAddr};
 
 pub struct ControllerPost;
 
-fn generate_slug(title: &str, existing_slugs: Vec<String>) -> String {
-    let base_slug = title
-        .to_lowercase()
-        .replace(" ", "-")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect::<String>();
+async fn generate_slug(title: &str) -> Result<String, (StatusCode, Json<Value>)> {
+    let base = ServicesPostSlug::slugify(title);
+    let taken = ModelPost::select_taken_slugs(&base, None)
+        .await
+        .map_err(|err| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao gerar slug: {}", err)
+                })),
+            )
+        })?;
+
+    Ok(ServicesPostSlug::unique(&base, &taken))
+}
+
+fn optional_claims(headers: &HeaderMap) -> Option<Claims> {
+    if !headers.contains_key(AUTHORIZATION) {
+        return None;
+    }
+    HelperMiddlewareToken::new().claims_from_headers(headers)
+}
 
-    let mut slug = base_slug.clone();
-    let mut counter = 1;
+// Leitores autenticados recebem também as próprias reações
+async fn attach_user_reactions(post: &mut Value, headers: &HeaderMap) {
+    let (Some(claims), Some(post_id)) = (optional_claims(headers), post["id"].as_i64()) else {
+        return;
+    };
 
-    while existing_slugs.contains(&slug) {
-        slug = format!("{}-{}", base_slug, counter);
-        counter += 1;
+    if let Ok(reactions) = ModelPost::select_user_reactions(post_id as i32, claims.user_id).await {
+        post["my_reactions"] = json!(reactions);
     }
+}
 
-    slug
+// Atrás de proxy reverso o IP do leitor vem nos cabeçalhos de encaminhamento,
+// que só são aceitos quando a conexão chega de um proxy em TRUSTED_PROXIES
+fn visitor_address(headers: &HeaderMap, address: SocketAddr) -> String {
+    dotenv().ok();
+    let trusted_proxies: Vec<IpAddr> = env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect();
+
+    forwarded_address(headers, address.ip(), &trusted_proxies).to_string()
+}
+
+fn forwarded_address(headers: &HeaderMap, peer: IpAddr, trusted_proxies: &[IpAddr]) -> IpAddr {
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+
+    // Cada proxy acrescenta o endereço anterior no fim; o primeiro não confiável
+    // da direita para a esquerda é o leitor, o restante pode ter sido forjado
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .split(',')
+                .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+                .rev()
+                .find(|ip| !trusted_proxies.contains(ip))
+        })
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        })
+        .unwrap_or(peer)
+}
+
+fn pagination(page: u32, per_page: u32, total: i64) -> Value {
+    let page = page as i64;
+    let per_page = per_page as i64;
+    let total_pages = (total + per_page - 1) / per_page;
+
+    json!({
+        "page": page,
+        "per_page": per_page,
+        "total": total,
+        "total_pages": total_pages,
+        "next_page": if page < total_pages { Some(page + 1) } else { None },
+        "prev_page": if page > 1 { Some(page - 1) } else { None },
+    })
+}
+
+// A data é comparada com o relógio do banco, que decide quando o post aparece
+async fn check_schedule(
+    status: Option<PostStatus>,
+    publication_date: Option<NaiveDateTime>,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    if status != Some(PostStatus::Scheduled) {
+        return Ok(());
+    }
+
+    let now = ModelPost::select_database_now().await.map_err(|err| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "status": false,
+                "message": format!("Erro ao validar agendamento: {}", err)
+            })),
+        )
+    })?;
+    validate_schedule(status, publication_date, now)
+}
+
+fn validate_schedule(
+    status: Option<PostStatus>,
+    publication_date: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    if status != Some(PostStatus::Scheduled) {
+        return Ok(());
+    }
+
+    match publication_date {
+        Some(date) if date > now => Ok(()),
+        Some(_) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "status": false,
+                "message": "A data de publicação de um post agendado deve estar no futuro"
+            })),
+        )),
+        None => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "status": false,
+                "message": "Posts agendados precisam de uma data de publicação"
+            })),
+        )),
+    }
+}
+
+fn expected_version(
+    headers: &HeaderMap,
+    body_version: Option<i32>,
+) -> Result<i32, (StatusCode, Json<Value>)> {
+    // If-Match aceita o ETag devolvido pelo GET, com ou sem aspas
+    let header_version = headers
+        .get(IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().trim_start_matches("W/").trim_matches('"'));
+
+    match (header_version, body_version) {
+        (Some(raw), _) => raw.parse::<i32>().map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "status": false, "message": "Cabeçalho If-Match inválido" })),
+            )
+        }),
+        (None, Some(version)) => Ok(version),
+        (None, None) => Err((
+            StatusCode::PRECONDITION_REQUIRED,
+            Json(json!({
+                "status": false,
+                "message": "Informe a versão do post em If-Match ou no campo version"
+            })),
+        )),
+    }
 }
 
 impl ControllerPost {
-    pub async fn get_all_posts() -> impl IntoResponse {
-        match ModelPost::select_post().await {
-            Ok(posts) => (
+    pub async fn get_all_posts(Query(filters): Query<PostListQuery>) -> impl IntoResponse {
+        Self::list_posts(filters, true).await
+    }
+
+    pub async fn search_posts(Query(search): Query<PostSearchQuery>) -> impl IntoResponse {
+        let terms = ServicesPostSearch::terms(search.q.as_deref().unwrap_or_default());
+        if terms.is_empty() {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "status": false,
+                    "message": format!(
+                        "Informe ao menos um termo de busca com {} caracteres",
+                        MIN_TERM_LENGTH
+                    )
+                })),
+            )
+                .into_response();
+        }
+
+        let boolean_query = ServicesPostSearch::boolean_query(&terms);
+        match ModelPost::search_posts(&search, &boolean_query).await {
+            Ok((mut posts, total)) => {
+                for post in posts.iter_mut() {
+                    let title = post["title"].as_str().unwrap_or_default().to_string();
+                    // O trecho sai do HTML renderizado, sem a sintaxe do markdown
+                    let body = format!(
+                        "{} {}",
+                        post["description"].as_str().unwrap_or_default(),
+                        post["content_html"].as_str().unwrap_or_default()
+                    );
+                    let plain = ServicesPostSearch::strip_tags(&body);
+
+                    post["highlight"] = json!({
+                        "title": ServicesPostSearch::highlight(&title, &terms, title.chars().count()),
+                        "snippet": ServicesPostSearch::highlight(&plain, &terms, 80),
+                    });
+                }
+
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "status": true,
+                        "data": posts,
+                        "pagination": pagination(search.page(), search.per_page(), total),
+                    })),
+                )
+                    .into_response()
+            }
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn search_posts_index(
+        Query(search): Query<PostIndexSearchQuery>,
+    ) -> impl IntoResponse {
+        let text = search.q.as_deref().unwrap_or_default().trim();
+        if text.is_empty() {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "status": false, "message": "Informe o termo de busca" })),
+            )
+                .into_response();
+        }
+
+        let instance = match ServicesPostSearchIndex::get_instance() {
+            Some(instance) => instance,
+            None => return HelpersResponse::error("Índice de busca não iniciado"),
+        };
+
+        let listing = PostSearchQuery {
+            page: search.page,
+            per_page: search.per_page,
+            ..Default::default()
+        };
+        let query = SearchIndexQuery {
+            text,
+            category_id: search.category_id,
+            author_id: search.author_id,
+            limit: listing.per_page() as usize,
+            offset: listing.offset() as usize,
+        };
+
+        match instance.search(&query) {
+            Ok(mut results) => {
+                let total = results["total"].as_i64().unwrap_or_default();
+                let mut facets = results["facets"].take();
+
+                // O índice guarda só os ids nas facetas; os nomes vêm do banco
+                let categories = ModelPost::get_all_categories().await.unwrap_or_default();
+                let authors = ModelPost::get_all_authors().await.unwrap_or_default();
+                for facet in facets["categories"].as_array_mut().into_iter().flatten() {
+                    let id = facet["id"].as_i64();
+                    facet["name"] = json!(categories
+                        .iter()
+                        .find(|category| Some(category.id as i64) == id)
+                        .map(|category| category.name.clone()));
+                }
+                for facet in facets["authors"].as_array_mut().into_iter().flatten() {
+                    let id = facet["id"].as_i64();
+                    facet["name"] = json!(authors
+                        .iter()
+                        .find(|author| Some(author.id as i64) == id)
+                        .map(|author| author.name.clone()));
+                }
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "status": true,
+                        "data": results["hits"].take(),
+                        "facets": facets,
+                        "pagination": pagination(listing.page(), listing.per_page(), total),
+                    })),
+                )
+                    .into_response()
+            }
+            Err(err) => HelpersResponse::error_with_detail("Erro ao pesquisar posts", err),
+        }
+    }
+
+    pub async fn rebuild_search_index() -> impl IntoResponse {
+        match ServicesPostSearchIndex::rebuild().await {
+            Ok(indexed) => {
+                HelpersResponse::success("Índice reconstruído!", json!({ "indexed": indexed }))
+            }
+            Err(err) => HelpersResponse::error_with_detail("Erro ao reconstruir índice", err),
+        }
+    }
+
+    pub async fn get_managed_posts(Query(filters): Query<PostListQuery>) -> impl IntoResponse {
+        Self::list_posts(filters, false).await
+    }
+
+    async fn list_posts(filters: PostListQuery, only_published: bool) -> Response {
+        match ModelPost::select_post(&filters, only_published).await {
+            Ok(page) => {
+                // Sem cursor a listagem segue aceitando ?page=, com os campos de página de antes
+                let mut envelope = if filters.uses_cursor() {
+                    json!({ "per_page": filters.per_page(), "total": page.total })
+                } else {
+                    pagination(filters.page(), filters.per_page(), page.total)
+                };
+                envelope["next_cursor"] = json!(page.next_cursor);
+                envelope["prev_cursor"] = json!(page.prev_cursor);
+                envelope["has_more"] = json!(page.next_cursor.is_some());
+
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "status": true,
+                        "data": page.posts,
+                        "pagination": envelope,
+                    })),
+                )
+                    .into_response()
+            }
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn get_all_categories() -> impl IntoResponse {
+        match ModelPost::get_all_categories().await {
+            Ok(categories) => (
                 StatusCode::OK,
-                Json(json!({ "status": true, "data": posts })),
+                Json(json!({ "status": true, "data": categories})),
             ),
             Err(err) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "status": false,
-                    "message": format!("Erro ao buscar posts: {}", err)
+                    "message": format!("Erro ao buscar categorias {}", err)
                 })),
             ),
         }
     }
 
-    pub async fn get_all_categories() -> impl IntoResponse {
-        match ModelPost::get_all_categories().await {
-            Ok(categories) => (
+    pub async fn get_category_tree() -> impl IntoResponse {
+        match ModelPost::select_category_tree().await {
+            Ok(tree) => (
                 StatusCode::OK,
-                Json(json!({ "status": true, "data": categories})),
+                Json(json!({ "status": true, "data": tree })),
             ),
             Err(err) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -74,27 +406,156 @@ impl ControllerPost {
         }
     }
 
-    pub async fn get_post_by_slug(Path(slug): Path<String>) -> impl IntoResponse {
-        match ModelPost::select_post_by_slug(slug).await {
-            Ok(post) => (
+    pub async fn get_author_by_id(Path(author_id): Path<i32>) -> impl IntoResponse {
+        match ModelPost::select_author_by_id(author_id).await {
+            Ok(author) => (
                 StatusCode::OK,
-                Json(json!({
-                     "status": true,
-                     "data": post,
-                })),
+                Json(json!({ "status": true, "data": author })),
             )
                 .into_response(),
             Err(err) => err.into_response(),
         }
     }
 
-    pub async fn get_post_by_id(Path(post_id): Path<i32>) -> impl IntoResponse {
+    pub async fn get_category_by_id(Path(category_id): Path<i32>) -> impl IntoResponse {
+        match ModelPost::select_category_by_id(category_id).await {
+            Ok(category) => (
+                StatusCode::OK,
+                Json(json!({ "status": true, "data": category })),
+            )
+                .into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn get_all_tags() -> impl IntoResponse {
+        match ModelPost::select_tags_with_counts().await {
+            Ok(tags) => (
+                StatusCode::OK,
+                Json(json!({ "status": true, "data": tags })),
+            ),
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao buscar tags: {}", err)
+                })),
+            ),
+        }
+    }
+
+    pub async fn get_post_by_slug(
+        ConnectInfo(address): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        Path(slug): Path<String>,
+    ) -> impl IntoResponse {
+        match ModelPost::select_post_by_slug(slug.clone()).await {
+            Ok(mut post) => {
+                attach_user_reactions(&mut post, &headers).await;
+
+                if let Some(post_id) = post["id"].as_i64() {
+                    let user_agent = headers
+                        .get(USER_AGENT)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default();
+                    ServicesPostViews::record(
+                        post_id as i32,
+                        &visitor_address(&headers, address),
+                        user_agent,
+                    );
+                }
+
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                         "status": true,
+                         "data": post,
+                    })),
+                )
+                    .into_response()
+            }
+            // Slugs antigos respondem com redirecionamento permanente para o atual
+            Err(err) => match ModelPost::select_redirect_slug(&slug).await {
+                Ok(Some(current_slug)) => (
+                    StatusCode::MOVED_PERMANENTLY,
+                    [(LOCATION, format!("/post/slug/{}", current_slug))],
+                    Json(json!({
+                        "status": false,
+                        "message": "Post movido para um novo endereço",
+                        "redirect_to": current_slug,
+                    })),
+                )
+                    .into_response(),
+                _ => err.into_response(),
+            },
+        }
+    }
+
+    pub async fn get_post_by_id(headers: HeaderMap, Path(post_id): Path<i32>) -> impl IntoResponse {
         match ModelPost::select_post_by_id(post_id).await {
-            Ok(post) => (
+            Ok(mut post) => {
+                attach_user_reactions(&mut post, &headers).await;
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "status": true,
+                        "data": post,
+                    })),
+                )
+                    .into_response()
+            }
+            Err(err) => err.into_response(),
+        }
+    }
+
+    pub async fn get_related_posts(
+        Path(post_id): Path<i32>,
+        Query(query): Query<RelatedPostsQuery>,
+    ) -> impl IntoResponse {
+        match ServicesPostRelated::related(post_id, query.limit() as usize).await {
+            Ok(Some(posts)) => (
                 StatusCode::OK,
                 Json(json!({
                     "status": true,
-                    "data": post,
+                    "data": posts,
+                })),
+            ),
+            Ok(None) => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Post não encontrado" })),
+            ),
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao buscar posts relacionados: {}", err)
+                })),
+            ),
+        }
+    }
+
+    pub async fn get_popular_posts(Query(query): Query<PopularPostsQuery>) -> impl IntoResponse {
+        let days = match query.days() {
+            Some(days) => days,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "status": false,
+                        "message": "Janela inválida, use por exemplo 24h, 7d ou 4w"
+                    })),
+                )
+                    .into_response()
+            }
+        };
+
+        match ModelPost::select_popular_posts(days, query.limit()).await {
+            Ok(posts) => (
+                StatusCode::OK,
+                Json(json!({
+                    "status": true,
+                    "data": posts,
+                    "window_days": days,
                 })),
             )
                 .into_response(),
@@ -102,6 +563,107 @@ impl ControllerPost {
         }
     }
 
+    pub async fn get_post_view_stats(Path(post_id): Path<i32>) -> impl IntoResponse {
+        match ModelPost::select_post_view_stats(post_id).await {
+            Ok(mut stats) => {
+                // Inclui as visualizações que ainda estão no buffer em memória
+                stats["pending"] = json!(ServicesPostViews::pending(post_id));
+                (
+                    StatusCode::OK,
+                    Json(json!({ "status": true, "data": stats })),
+                )
+            }
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao buscar visualizações: {}", err)
+                })),
+            ),
+        }
+    }
+
+    pub async fn get_post_reactions(
+        headers: HeaderMap,
+        Path(post_id): Path<i32>,
+    ) -> impl IntoResponse {
+        match ModelPost::select_reaction_counts(post_id).await {
+            Ok(counts) => {
+                let mut data = json!({ "post_id": post_id, "reactions": counts });
+                if let Some(claims) = optional_claims(&headers) {
+                    data["my_reactions"] =
+                        json!(ModelPost::select_user_reactions(post_id, claims.user_id)
+                            .await
+                            .unwrap_or_default());
+                }
+                (
+                    StatusCode::OK,
+                    Json(json!({ "status": true, "data": data })),
+                )
+            }
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao buscar reações: {}", err)
+                })),
+            ),
+        }
+    }
+
+    pub async fn toggle_reaction(
+        Extension(claims): Extension<Claims>,
+        Path(post_id): Path<i32>,
+        Json(request): Json<ReactionRequest>,
+    ) -> impl IntoResponse {
+        let reacted =
+            match ModelPost::toggle_reaction(post_id, claims.user_id, request.reaction).await {
+                Ok(reacted) => reacted,
+                Err(err) => return err.into_response(),
+            };
+
+        let counts = ModelPost::select_reaction_counts(post_id)
+            .await
+            .unwrap_or_default();
+        let mine = ModelPost::select_user_reactions(post_id, claims.user_id)
+            .await
+            .unwrap_or_default();
+
+        (
+            StatusCode::OK,
+            Json(json!({
+                "status": true,
+                "message": if reacted { "Reação registrada" } else { "Reação removida" },
+                "data": {
+                    "post_id": post_id,
+                    "reaction": request.reaction,
+                    "reacted": reacted,
+                    "reactions": counts,
+                    "my_reactions": mine,
+                },
+            })),
+        )
+            .into_response()
+    }
+
+    pub async fn get_managed_post(Path(post_id): Path<i32>) -> impl IntoResponse {
+        match ModelPost::select_post_for_edit(post_id).await {
+            Ok(post) => {
+                let etag = format!("\"{}\"", post["version"]);
+                (
+                    StatusCode::OK,
+                    [(ETAG, etag)],
+                    Json(json!({
+                        "status": true,
+                        "data": post,
+                    })),
+                )
+                    .into_response()
+            }
+            Err(err) => err.into_response(),
+        }
+    }
+
     pub async fn create_author(
         Json(create_author): Json<CreateAuthor>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
@@ -114,18 +676,90 @@ impl ControllerPost {
         Ok(ModelPost::create_category(create_category).await)
     }
 
+    pub async fn edit_author(
+        Json(edit_author): Json<EditAuthor>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::edit_author(edit_author).await)
+    }
+
+    pub async fn delete_author(
+        Json(delete_author): Json<DeleteAuthor>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::delete_author(delete_author).await)
+    }
+
+    pub async fn edit_category(
+        Json(edit_category): Json<EditCategory>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::edit_category(edit_category).await)
+    }
+
+    pub async fn delete_category(
+        Json(delete_category): Json<DeleteCategory>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::delete_category(delete_category).await)
+    }
+
+    pub async fn create_tag(
+        Json(create_tag): Json<CreateTag>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::create_tag(create_tag).await)
+    }
+
+    pub async fn edit_tag(
+        Json(edit_tag): Json<EditTag>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::edit_tag(edit_tag).await)
+    }
+
+    pub async fn delete_tag(
+        Json(delete_tag): Json<DeleteTag>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::delete_tag(delete_tag).await)
+    }
+
     pub async fn create_post(
+        Extension(claims): Extension<Claims>,
         Json(create_post): Json<PostRequest>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-        let existing_slugs = ModelPost::get_all_slugs().await.unwrap_or(vec![]);
-        let slug = generate_slug(&create_post.post.title, existing_slugs);
-        Ok(ModelPost::create_post(&slug, create_post).await)
+        check_schedule(create_post.post.status, create_post.post.publication_date).await?;
+
+        let slug = generate_slug(&create_post.post.title).await?;
+        Ok(ModelPost::create_post(&slug, create_post, claims.user_id).await)
     }
 
     pub async fn edit_post(
-        Json(edit_post): Json<EditPost>,
+        Extension(claims): Extension<Claims>,
+        headers: HeaderMap,
+        Json(mut edit_post): Json<EditPost>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-        Ok(ModelPost::edit_post(edit_post).await)
+        let version = expected_version(&headers, edit_post.post.version)?;
+        check_schedule(edit_post.post.status, edit_post.post.publication_date).await?;
+
+        // Slugs informados manualmente passam pela mesma normalização da criação
+        edit_post.post.slug = ServicesPostSlug::slugify(&edit_post.post.slug);
+        let taken = ModelPost::select_taken_slugs(&edit_post.post.slug, Some(edit_post.post.id))
+            .await
+            .map_err(|err| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "status": false,
+                        "message": format!("Erro ao verificar slug: {}", err)
+                    })),
+                )
+            })?;
+        if taken.contains(&edit_post.post.slug) {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "status": false,
+                    "message": "Slug já utilizado por outro post"
+                })),
+            ));
+        }
+
+        Ok(ModelPost::edit_post(edit_post, version, claims.user_id).await)
     }
 
     pub async fn delete_post(
@@ -133,4 +767,166 @@ impl ControllerPost {
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
         Ok(ModelPost::delete_post(delete_post).await)
     }
+
+    pub async fn get_trashed_posts() -> impl IntoResponse {
+        match ModelPost::select_trashed_posts().await {
+            Ok(posts) => (
+                StatusCode::OK,
+                Json(json!({ "status": true, "data": posts })),
+            ),
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao buscar lixeira: {}", err)
+                })),
+            ),
+        }
+    }
+
+    pub async fn restore_post(
+        Json(restore_post): Json<DeletePost>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::restore_post(restore_post).await)
+    }
+
+    pub async fn purge_trashed_posts() -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        dotenv().ok();
+        let retention_days = env::var("POST_TRASH_RETENTION_DAYS")
+            .ok()
+            .and_then(|days| days.parse::<u32>().ok())
+            .unwrap_or(30);
+
+        Ok(ModelPost::purge_trashed_posts(retention_days).await)
+    }
+
+    pub async fn get_post_revisions(Path(post_id): Path<i32>) -> impl IntoResponse {
+        match ModelPost::select_revisions(post_id).await {
+            Ok(revisions) => (
+                StatusCode::OK,
+                Json(json!({ "status": true, "data": revisions })),
+            ),
+            Err(err) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": false,
+                    "message": format!("Erro ao buscar revisões: {}", err)
+                })),
+            ),
+        }
+    }
+
+    pub async fn get_revision_diff(
+        Path(post_id): Path<i32>,
+        Query(range): Query<RevisionDiffQuery>,
+    ) -> impl IntoResponse {
+        let from = match ModelPost::select_revision(post_id, range.from).await {
+            Ok(revision) => revision,
+            Err(err) => return err.into_response(),
+        };
+        let to = match ModelPost::select_revision(post_id, range.to).await {
+            Ok(revision) => revision,
+            Err(err) => return err.into_response(),
+        };
+
+        (
+            StatusCode::OK,
+            Json(json!({
+                "status": true,
+                "data": ModelPost::diff_revisions(&from, &to),
+            })),
+        )
+            .into_response()
+    }
+
+    pub async fn rollback_post(
+        Extension(claims): Extension<Claims>,
+        Path((post_id, revision_id)): Path<(i32, i32)>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        Ok(ModelPost::rollback_post(post_id, revision_id, claims.user_id).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn expected_version_prefers_if_match_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, "W/\"7\"".parse().unwrap());
+
+        assert_eq!(expected_version(&headers, Some(3)).ok(), Some(7));
+        assert_eq!(expected_version(&HeaderMap::new(), Some(3)).ok(), Some(3));
+    }
+
+    #[test]
+    fn expected_version_rejects_missing_or_invalid_versions() {
+        let missing = expected_version(&HeaderMap::new(), None).unwrap_err();
+        assert_eq!(missing.0, StatusCode::PRECONDITION_REQUIRED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, "\"abc\"".parse().unwrap());
+        let invalid = expected_version(&headers, None).unwrap_err();
+        assert_eq!(invalid.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn validate_schedule_requires_a_future_date_for_scheduled_posts() {
+        let now = date("2026-01-01 12:00:00");
+        let scheduled = Some(PostStatus::Scheduled);
+
+        assert!(validate_schedule(scheduled, Some(date("2026-01-02 08:00:00")), now).is_ok());
+        assert!(validate_schedule(scheduled, Some(now), now).is_err());
+        assert!(validate_schedule(scheduled, None, now).is_err());
+    }
+
+    #[test]
+    fn validate_schedule_ignores_other_statuses() {
+        let now = date("2026-01-01 12:00:00");
+
+        assert!(validate_schedule(Some(PostStatus::Draft), None, now).is_ok());
+        assert!(validate_schedule(None, Some(date("2020-01-01 00:00:00")), now).is_ok());
+    }
+
+    fn forwarded_headers(forwarded_for: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", forwarded_for.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn forwarded_address_ignores_headers_from_untrusted_peers() {
+        let headers = forwarded_headers("203.0.113.7");
+        let peer: IpAddr = "198.51.100.1".parse().unwrap();
+
+        assert_eq!(forwarded_address(&headers, peer, &[]), peer);
+    }
+
+    #[test]
+    fn forwarded_address_skips_trusted_proxies_and_spoofed_entries() {
+        let headers = forwarded_headers("1.1.1.1, 203.0.113.7, 10.0.0.2");
+        let trusted: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+
+        assert_eq!(
+            forwarded_address(&headers, trusted[0], &trusted),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn forwarded_address_falls_back_to_real_ip_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", "203.0.113.9".parse().unwrap());
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+
+        assert_eq!(
+            forwarded_address(&headers, proxy, &[proxy]),
+            "203.0.113.9".parse::<IpAddr>().unwrap()
+        );
+    }
 }
@@ -0,0 +1,117 @@
+use crate::mvc::models::post::model_post::ModelPost;
+use crate::mvc::services::post::sitemap::services_post_sitemap::{ServicesPostSitemap, SitemapUrl};
+use axum::{
+    extract::Path,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use dotenv::dotenv;
+use serde_json::json;
+use std::env;
+
+pub struct ControllerSitemap;
+
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+fn api_url() -> String {
+    dotenv().ok();
+    let site_url = env::var("BASE_URL").expect("BASE_URL não configurada");
+    env::var("API_URL").unwrap_or(site_url)
+}
+
+impl ControllerSitemap {
+    async fn collect_urls() -> Result<Vec<SitemapUrl>, sqlx::Error> {
+        dotenv().ok();
+        let site_url = env::var("BASE_URL").expect("BASE_URL não configurada");
+        let site_url = site_url.trim_end_matches('/');
+
+        let mut urls: Vec<SitemapUrl> = ModelPost::get_published_slugs()
+            .await?
+            .into_iter()
+            .map(|(slug, lastmod)| SitemapUrl {
+                loc: format!("{}/post/{}", site_url, slug),
+                lastmod,
+            })
+            .collect();
+
+        for (column, path) in [("category_id", "category"), ("author_id", "author")] {
+            let entries = ModelPost::get_sitemap_taxonomies(column).await?;
+            urls.extend(entries.into_iter().map(|(id, lastmod)| SitemapUrl {
+                loc: format!("{}/{}/{}", site_url, path, id),
+                lastmod,
+            }));
+        }
+
+        Ok(urls)
+    }
+
+    fn sitemap_error(err: sqlx::Error) -> Response {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "status": false,
+                "message": format!("Erro ao gerar sitemap: {}", err)
+            })),
+        )
+            .into_response()
+    }
+
+    pub async fn sitemap() -> impl IntoResponse {
+        let urls = match Self::collect_urls().await {
+            Ok(urls) => urls,
+            Err(err) => return Self::sitemap_error(err),
+        };
+
+        // Acima do limite do protocolo, vira um índice apontando para as partes
+        let body = if urls.len() > ServicesPostSitemap::MAX_URLS_PER_SITEMAP {
+            ServicesPostSitemap::index(&api_url(), &urls)
+        } else {
+            ServicesPostSitemap::urlset(&urls)
+        };
+
+        (StatusCode::OK, [(CONTENT_TYPE, XML_CONTENT_TYPE)], body).into_response()
+    }
+
+    pub async fn sitemap_page(Path(page): Path<usize>) -> impl IntoResponse {
+        let urls = match Self::collect_urls().await {
+            Ok(urls) => urls,
+            Err(err) => return Self::sitemap_error(err),
+        };
+
+        match page.checked_sub(1).and_then(|index| {
+            urls.chunks(ServicesPostSitemap::MAX_URLS_PER_SITEMAP)
+                .nth(index)
+        }) {
+            Some(chunk) => (
+                StatusCode::OK,
+                [(CONTENT_TYPE, XML_CONTENT_TYPE)],
+                ServicesPostSitemap::urlset(chunk),
+            )
+                .into_response(),
+            None => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Sitemap não encontrado" })),
+            )
+                .into_response(),
+        }
+    }
+
+    pub async fn robots() -> impl IntoResponse {
+        dotenv().ok();
+        let user_agent = env::var("ROBOTS_USER_AGENT").unwrap_or_else(|_| "*".to_string());
+        let disallow: Vec<String> = env::var("ROBOTS_DISALLOW")
+            .unwrap_or_default()
+            .split(',')
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect();
+        let sitemap_url = format!("{}/sitemap.xml", api_url().trim_end_matches('/'));
+
+        (
+            StatusCode::OK,
+            [(CONTENT_TYPE, "text/plain; charset=utf-8")],
+            ServicesPostSitemap::robots(&user_agent, &disallow, &sitemap_url),
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -8,9 +8,16 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
-use sqlx::Row;
+use dotenv::dotenv;
+use sqlx::{mysql::MySqlRow, Row};
+use std::collections::{HashMap, HashSet};
 
 use crate::helpers::db::helpers_mysql::HelperMySql;
+use crate::helpers::sanitizer::helpers_sanitizer::{HelpersSanitizer, SanitizePolicy};
+use crate::mvc::models::post::model_post::ReactionType;
+use crate::mvc::services::comment::spam::services_comment_spam::{
+    ServicesCommentSpam, SpamVerdict,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CommentRequest {
@@ -24,6 +31,68 @@ pub struct CommentRequestSchema {
     parent_id: Option<i32>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Spam,
+}
+
+impl CommentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+            CommentStatus::Spam => "spam",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModerateComments {
+    pub ids: Vec<i32>,
+    pub status: CommentStatus,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ModerationQueueQuery {
+    pub status: Option<CommentStatus>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+impl ModerationQueueQuery {
+    pub const DEFAULT_PER_PAGE: u32 = 20;
+    pub const MAX_PER_PAGE: u32 = 100;
+
+    pub fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u32 {
+        self.per_page
+            .unwrap_or(Self::DEFAULT_PER_PAGE)
+            .clamp(1, Self::MAX_PER_PAGE)
+    }
+
+    pub fn offset(&self) -> u32 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditCommentRequest {
+    pub comment: EditCommentSchema,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditCommentSchema {
+    content: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Comment {
     id: i32,
@@ -35,6 +104,88 @@ pub struct Comment {
     updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct CommentListQuery {
+    pub tree: Option<bool>,
+    pub max_depth: Option<u32>,
+    pub root_id: Option<i32>,
+    pub sort: Option<CommentSort>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentSort {
+    #[default]
+    Oldest,
+    Newest,
+    #[serde(alias = "most-reacted")]
+    MostReacted,
+}
+
+impl CommentSort {
+    // Chave de ordenação calculada no banco; é também a primeira parte do cursor
+    fn key_expression(&self) -> &'static str {
+        match self {
+            CommentSort::Oldest | CommentSort::Newest => {
+                "CAST(UNIX_TIMESTAMP(c.created_at) AS SIGNED)"
+            }
+            CommentSort::MostReacted => {
+                "IF(c.is_deleted = 0 AND c.status = 'approved', \
+                    (SELECT COUNT(*) FROM comment_reactions r WHERE r.comment_id = c.id), 0)"
+            }
+        }
+    }
+
+    fn direction(&self) -> (&'static str, &'static str) {
+        match self {
+            CommentSort::Oldest => ("ASC", ">"),
+            CommentSort::Newest | CommentSort::MostReacted => ("DESC", "<"),
+        }
+    }
+}
+
+impl CommentListQuery {
+    pub const MAX_DEPTH_LIMIT: u32 = 20;
+    pub const DEFAULT_LIMIT: u32 = 20;
+    pub const MAX_LIMIT: u32 = 100;
+
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, Self::MAX_LIMIT) as usize
+    }
+
+    // O cursor é "<chave>_<id>", com a chave dependendo da ordenação escolhida
+    pub fn cursor(&self) -> Result<Option<(i64, i32)>, ApiError> {
+        let Some(cursor) = self.cursor.as_deref().filter(|cursor| !cursor.is_empty()) else {
+            return Ok(None);
+        };
+
+        cursor
+            .split_once('_')
+            .and_then(|(key, id)| Some((key.parse::<i64>().ok()?, id.parse::<i32>().ok()?)))
+            .map(Some)
+            .ok_or(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Cursor inválido".to_string(),
+            })
+    }
+
+    pub fn max_depth(&self) -> u32 {
+        dotenv().ok();
+        let default_depth = std::env::var("COMMENT_TREE_MAX_DEPTH")
+            .ok()
+            .and_then(|depth| depth.parse::<u32>().ok())
+            .unwrap_or(5);
+
+        self.max_depth
+            .unwrap_or(default_depth)
+            .clamp(1, Self::MAX_DEPTH_LIMIT)
+    }
+}
+
 #[derive(Debug)]
 pub struct ApiError {
     status_code: StatusCode,
@@ -56,13 +207,198 @@ impl IntoResponse for ApiError {
 
 pub struct ModelComment;
 
+pub const REMOVED_PLACEHOLDER: &str = "[removed]";
+
+// Comentários públicos do post e todos os seus ancestrais: removidos continuam como
+// marcadores enquanto tiverem alguma resposta ativa abaixo deles, para não quebrar a conversa
+const VISIBLE_COMMENTS: &str = r#"
+    visible AS (
+        SELECT id, parent_id FROM comments
+        WHERE post_id = ? AND is_deleted = 0 AND status = 'approved'
+        UNION DISTINCT
+        SELECT parent.id, parent.parent_id FROM comments parent
+        INNER JOIN visible v ON parent.id = v.parent_id
+    )
+"#;
+
+const COMMENT_COLUMNS: &str = r#"
+    u.id AS user_id,
+    u.name AS user_name,
+    c.*,
+    (SELECT COUNT(*) FROM comment_reactions r WHERE r.comment_id = c.id) AS reaction_count
+"#;
+
+// Visível ao público: não removido e aprovado pela moderação
+fn is_public(row: &MySqlRow) -> bool {
+    !row.try_get::<bool, _>("is_deleted").unwrap_or(false)
+        && row.try_get::<String, _>("status").unwrap_or_default()
+            == CommentStatus::Approved.as_str()
+}
+
+fn comment_row_to_json(row: &MySqlRow) -> serde_json::Value {
+    let edited_at = row
+        .try_get::<Option<DateTime<Utc>>, _>("edited_at")
+        .unwrap_or(None);
+
+    // Removidos e reprovados só aparecem quando ainda têm respostas, sem autor nem conteúdo
+    if !is_public(row) {
+        return json!({
+            "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+            "post_id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
+            "user_id": null,
+            "user_name": null,
+            "content": REMOVED_PLACEHOLDER,
+            "reaction_count": 0,
+            "is_deleted": true,
+            "edited": false,
+            "edited_at": null,
+            "parent_id": row.try_get::<Option<i32>, _>("parent_id").unwrap_or(None),
+            "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_else(|_| Utc::now()),
+            "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_else(|_| Utc::now())
+        });
+    }
+
+    json!({
+        "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+        "post_id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
+        "user_id": row.try_get::<i32, _>("user_id").unwrap_or_default(),
+        "user_name": row.try_get::<Option<String>, _>("user_name").unwrap_or(None),
+        "content": row.try_get::<String, _>("content").unwrap_or_default(),
+        "reaction_count": row.try_get::<i64, _>("reaction_count").unwrap_or_default(),
+        "is_deleted": false,
+        "edited": edited_at.is_some(),
+        "edited_at": edited_at,
+        "parent_id": row.try_get::<Option<i32>, _>("parent_id").unwrap_or(None),
+        "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_else(|_| Utc::now()),
+        "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_else(|_| Utc::now())
+    })
+}
+
+// Monta a árvore de respostas; abaixo de `max_depth` o ramo vem recolhido,
+// apenas com a contagem, e pode ser pedido depois via `root_id`
+fn build_comment_tree(
+    children: &HashMap<Option<i32>, Vec<&serde_json::Value>>,
+    parent_id: Option<i32>,
+    depth: u32,
+    max_depth: u32,
+) -> Vec<serde_json::Value> {
+    children
+        .get(&parent_id)
+        .into_iter()
+        .flatten()
+        .map(|comment| {
+            let id = comment["id"].as_i64().map(|id| id as i32);
+            let direct_replies = children.get(&id).map(Vec::len).unwrap_or_default();
+            let collapsed = depth >= max_depth && direct_replies > 0;
+
+            let mut node = (*comment).clone();
+            node["depth"] = json!(depth);
+            node["reply_count"] = json!(direct_replies);
+            node["total_replies"] = json!(count_replies(children, id));
+            node["collapsed"] = json!(collapsed);
+            node["replies"] = if collapsed {
+                json!([])
+            } else {
+                json!(build_comment_tree(children, id, depth + 1, max_depth))
+            };
+            node
+        })
+        .collect()
+}
+
+fn count_replies(
+    children: &HashMap<Option<i32>, Vec<&serde_json::Value>>,
+    id: Option<i32>,
+) -> usize {
+    children
+        .get(&id)
+        .into_iter()
+        .flatten()
+        .map(|reply| 1 + count_replies(children, reply["id"].as_i64().map(|id| id as i32)))
+        .sum()
+}
+
 impl ModelComment {
-    pub async fn insert_comment(new_comment: CommentRequest, user_id: i32) -> Result<(), ApiError> {
+    // Quantos comentários aprovados um usuário precisa para publicar sem moderação
+    fn auto_approve_after() -> i64 {
+        dotenv().ok();
+        std::env::var("COMMENT_AUTO_APPROVE_AFTER")
+            .ok()
+            .and_then(|count| count.parse::<i64>().ok())
+            .unwrap_or(1)
+    }
+
+    async fn initial_status(user_id: i32, is_admin: bool) -> Result<CommentStatus, ApiError> {
+        let required = Self::auto_approve_after();
+        if is_admin || required <= 0 {
+            return Ok(CommentStatus::Approved);
+        }
+
+        let query = "SELECT COUNT(*) AS approved FROM comments WHERE user_id = ? AND status = 'approved' AND is_deleted = 0";
+        let approved = HelperMySql::execute_query_with_params(query, vec![user_id])
+            .await
+            .map_err(|err| ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao verificar histórico do usuário: {}", err),
+            })?
+            .first()
+            .and_then(|row| row.try_get::<i64, _>("approved").ok())
+            .unwrap_or_default();
+
+        Ok(if approved >= required {
+            CommentStatus::Approved
+        } else {
+            CommentStatus::Pending
+        })
+    }
+
+    // `comment_id` identifica o comentário editado, que não conta como duplicado de si mesmo
+    async fn moderation_status(
+        content: &str,
+        user_id: i32,
+        is_admin: bool,
+        comment_id: Option<i32>,
+    ) -> Result<(CommentStatus, SpamVerdict), ApiError> {
+        let mut status = Self::initial_status(user_id, is_admin).await?;
+
+        // Pontuação alta segura o comentário na fila mesmo de usuários confiáveis;
+        // acima do dobro do limite ele já entra marcado como spam
+        let verdict = ServicesCommentSpam::score(content, user_id, comment_id).await;
+        let threshold = ServicesCommentSpam::threshold();
+        if !is_admin && verdict.score >= threshold * 2.0 {
+            status = CommentStatus::Spam;
+        } else if !is_admin && verdict.score >= threshold {
+            status = CommentStatus::Pending;
+        }
+
+        Ok((status, verdict))
+    }
+
+    pub async fn insert_comment(
+        new_comment: CommentRequest,
+        user_id: i32,
+        is_admin: bool,
+    ) -> Result<CommentStatus, ApiError> {
         let now_utc = Utc::now();
+        let content =
+            HelpersSanitizer::clean(&new_comment.comment.content, SanitizePolicy::Comment);
+
+        if content.trim().is_empty() {
+            return Err(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Comentário vazio".to_string(),
+            });
+        }
+
+        if let Some(parent_id) = new_comment.comment.parent_id {
+            Self::validate_parent(parent_id, new_comment.comment.post_id).await?;
+        }
+
+        let (status, verdict) = Self::moderation_status(&content, user_id, is_admin, None).await?;
 
         let query = r#"
-        INSERT INTO comments (post_id, user_id, content, is_deleted, created_at, updated_at, parent_id) 
-        VALUES (?, ?, ?, ?, ?, ?, ?)
+        INSERT INTO comments (post_id, user_id, content, is_deleted, created_at, updated_at, parent_id, status, spam_score, spam_reasons)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         match HelperMySql::get_instance() {
@@ -70,16 +406,19 @@ impl ModelComment {
                 let result = sqlx::query(query)
                     .bind(new_comment.comment.post_id)
                     .bind(user_id)
-                    .bind(&new_comment.comment.content)
+                    .bind(&content)
                     .bind(0) // is_deleted
                     .bind(now_utc)
                     .bind(now_utc)
                     .bind(new_comment.comment.parent_id) // Option<i32> diretamente
+                    .bind(status.as_str())
+                    .bind(verdict.score)
+                    .bind(verdict.reasons.join("; "))
                     .execute(&instance.pool)
                     .await;
 
                 match result {
-                    Ok(_) => Ok(()),
+                    Ok(_) => Ok(status),
                     Err(err) => Err(ApiError {
                         status_code: StatusCode::INTERNAL_SERVER_ERROR,
                         message: format!("Erro ao inserir comentário: {}", err),
@@ -93,48 +432,726 @@ impl ModelComment {
         }
     }
 
-    pub async fn select_comment_by_post(post_id: i32) -> Result<serde_json::Value, ApiError> {
-        let query = r#"
-        SELECT 
-            u.id AS user_id,
-            u.name AS user_name,
-            c.*
-        FROM 
-            comments c
-        LEFT JOIN 
-            users u ON c.user_id = u.id
-        WHERE 
-            c.post_id = ? AND c.is_deleted = 0;
+    // A resposta só é aceita se o comentário pai existir, for do mesmo post e estiver ativo
+    async fn validate_parent(parent_id: i32, post_id: i32) -> Result<(), ApiError> {
+        let query = "SELECT post_id, is_deleted, status FROM comments WHERE id = ?";
+        let rows = HelperMySql::execute_query_with_params(query, vec![parent_id])
+            .await
+            .map_err(|err| ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar comentário pai: {}", err),
+            })?;
 
-        "#;
+        let parent = rows.first().ok_or(ApiError {
+            status_code: StatusCode::NOT_FOUND,
+            message: "Comentário pai não encontrado".to_string(),
+        })?;
 
-        let params: Vec<i32> = vec![post_id];
-        match HelperMySql::execute_query_with_params(query, params).await {
-            Ok(rows) => {
-                let comments: Vec<serde_json::Value> = rows
-                    .iter()
-                    .map(|row| {
+        if parent.try_get::<i32, _>("post_id").unwrap_or_default() != post_id {
+            return Err(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "O comentário pai pertence a outro post".to_string(),
+            });
+        }
 
-                        json!({
-                            "id": row.try_get::<i32, _>("id").unwrap_or_default(),
-                            "post_id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
-                            "user_id": row.try_get::<i32, _>("user_id").unwrap_or_default(),
-                            "user_name": row.try_get::<Option<String>, _>("user_name").unwrap_or(None),
-                            "content": row.try_get::<String, _>("content").unwrap_or_default(),
-                            "is_deleted": row.try_get::<bool, _>("is_deleted").unwrap_or(false),
-                            "parent_id": row.try_get::<Option<i32>, _>("parent_id").unwrap_or(None),
-                            "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_else(|_| Utc::now()),
-                            "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_else(|_| Utc::now())
-                        })
-                    })
-                    .collect();
+        if !is_public(parent) {
+            return Err(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Não é possível responder a um comentário removido".to_string(),
+            });
+        }
+
+        Ok(())
+    }
 
-                Ok(json!({"comments": comments}))
+    async fn select_owned_comment(
+        comment_id: i32,
+        user_id: i32,
+        is_admin: bool,
+    ) -> Result<MySqlRow, ApiError> {
+        let query = "SELECT * FROM comments WHERE id = ? AND is_deleted = 0";
+        let rows = HelperMySql::execute_query_with_params(query, vec![comment_id])
+            .await
+            .map_err(|err| ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar comentário: {}", err),
+            })?;
+
+        let comment = rows.into_iter().next().ok_or(ApiError {
+            status_code: StatusCode::NOT_FOUND,
+            message: "Comentário não encontrado".to_string(),
+        })?;
+
+        if !is_admin && comment.try_get::<i32, _>("user_id").unwrap_or_default() != user_id {
+            return Err(ApiError {
+                status_code: StatusCode::FORBIDDEN,
+                message: "Apenas o autor do comentário pode alterá-lo".to_string(),
+            });
+        }
+
+        Ok(comment)
+    }
+
+    fn edit_window() -> Duration {
+        dotenv().ok();
+        let minutes = std::env::var("COMMENT_EDIT_WINDOW_MINUTES")
+            .ok()
+            .and_then(|minutes| minutes.parse::<i64>().ok())
+            .unwrap_or(15);
+        Duration::minutes(minutes)
+    }
+
+    pub async fn update_comment(
+        comment_id: i32,
+        edit: EditCommentRequest,
+        user_id: i32,
+        is_admin: bool,
+    ) -> Result<serde_json::Value, ApiError> {
+        let comment = Self::select_owned_comment(comment_id, user_id, is_admin).await?;
+
+        // Administradores podem corrigir comentários a qualquer momento
+        let created_at = comment
+            .try_get::<DateTime<Utc>, _>("created_at")
+            .unwrap_or_else(|_| Utc::now());
+        if !is_admin && Utc::now() - created_at > Self::edit_window() {
+            return Err(ApiError {
+                status_code: StatusCode::FORBIDDEN,
+                message: "O prazo para editar este comentário terminou".to_string(),
+            });
+        }
+
+        let content = HelpersSanitizer::clean(&edit.comment.content, SanitizePolicy::Comment);
+        if content.trim().is_empty() {
+            return Err(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Comentário vazio".to_string(),
+            });
+        }
+
+        // A edição passa pela mesma moderação da criação; decisões de rejeição ou spam
+        // de um moderador continuam valendo e edições de administradores não são avaliadas
+        let current_status = comment.try_get::<String, _>("status").unwrap_or_default();
+        let moderated = !is_admin
+            && current_status != CommentStatus::Rejected.as_str()
+            && current_status != CommentStatus::Spam.as_str();
+        let moderation = if moderated {
+            Some(Self::moderation_status(&content, user_id, is_admin, Some(comment_id)).await?)
+        } else {
+            None
+        };
+
+        let now_utc = Utc::now();
+        let query = match moderation {
+            Some(_) => "UPDATE comments SET content = ?, edited_at = ?, updated_at = ?, status = ?, spam_score = ?, spam_reasons = ? WHERE id = ?",
+            None => "UPDATE comments SET content = ?, edited_at = ?, updated_at = ? WHERE id = ?",
+        };
+        match HelperMySql::get_instance() {
+            Some(instance) => {
+                let mut statement = sqlx::query(query)
+                    .bind(&content)
+                    .bind(now_utc)
+                    .bind(now_utc);
+                if let Some((status, verdict)) = &moderation {
+                    statement = statement
+                        .bind(status.as_str())
+                        .bind(verdict.score)
+                        .bind(verdict.reasons.join("; "));
+                }
+                let result = statement.bind(comment_id).execute(&instance.pool).await;
+
+                let status = match &moderation {
+                    Some((status, _)) => status.as_str().to_string(),
+                    None => current_status,
+                };
+                match result {
+                    Ok(_) => Ok(json!({
+                        "id": comment_id,
+                        "content": content,
+                        "edited": true,
+                        "edited_at": now_utc,
+                        "moderation_status": status,
+                    })),
+                    Err(err) => Err(ApiError {
+                        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: format!("Erro ao editar comentário: {}", err),
+                    }),
+                }
             }
-            Err(err) => Err(ApiError {
+            None => Err(ApiError {
                 status_code: StatusCode::INTERNAL_SERVER_ERROR,
-                message: format!("Erro ao buscar comentários: {}", err),
+                message: "Database not initialized".to_string(),
             }),
         }
     }
+
+    pub async fn delete_comment(
+        comment_id: i32,
+        user_id: i32,
+        is_admin: bool,
+    ) -> Result<(), ApiError> {
+        Self::select_owned_comment(comment_id, user_id, is_admin).await?;
+
+        let query = "UPDATE comments SET is_deleted = 1, updated_at = ? WHERE id = ?";
+        match HelperMySql::get_instance() {
+            Some(instance) => {
+                let result = sqlx::query(query)
+                    .bind(Utc::now())
+                    .bind(comment_id)
+                    .execute(&instance.pool)
+                    .await;
+
+                match result {
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(ApiError {
+                        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: format!("Erro ao remover comentário: {}", err),
+                    }),
+                }
+            }
+            None => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Database not initialized".to_string(),
+            }),
+        }
+    }
+
+    pub async fn select_moderation_queue(
+        filters: &ModerationQueueQuery,
+    ) -> Result<(Vec<serde_json::Value>, i64), ApiError> {
+        let status = filters.status.unwrap_or(CommentStatus::Pending);
+        let error = |err: sqlx::Error| ApiError {
+            status_code: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Erro ao buscar fila de moderação: {}", err),
+        };
+
+        let count_query =
+            "SELECT COUNT(*) AS total FROM comments WHERE status = ? AND is_deleted = 0";
+        let total = HelperMySql::execute_query_with_params(count_query, vec![status.as_str()])
+            .await
+            .map_err(error)?
+            .first()
+            .and_then(|row| row.try_get::<i64, _>("total").ok())
+            .unwrap_or_default();
+
+        let query = format!(
+            r#"
+            SELECT c.*, u.name AS user_name, p.title AS post_title
+            FROM comments c
+            LEFT JOIN users u ON c.user_id = u.id
+            LEFT JOIN posts p ON c.post_id = p.id
+            WHERE c.status = ? AND c.is_deleted = 0
+            ORDER BY c.created_at ASC, c.id ASC
+            LIMIT {} OFFSET {}
+            "#,
+            filters.per_page(),
+            filters.offset()
+        );
+
+        let rows = HelperMySql::execute_query_with_params(&query, vec![status.as_str()])
+            .await
+            .map_err(error)?;
+        let comments = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+                    "post_id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
+                    "post_title": row.try_get::<Option<String>, _>("post_title").unwrap_or(None),
+                    "user_id": row.try_get::<i32, _>("user_id").unwrap_or_default(),
+                    "user_name": row.try_get::<Option<String>, _>("user_name").unwrap_or(None),
+                    "content": row.try_get::<String, _>("content").unwrap_or_default(),
+                    "parent_id": row.try_get::<Option<i32>, _>("parent_id").unwrap_or(None),
+                    "status": row.try_get::<String, _>("status").unwrap_or_default(),
+                    "spam_score": row.try_get::<Option<f64>, _>("spam_score").unwrap_or(None),
+                    "spam_reasons": row
+                        .try_get::<Option<String>, _>("spam_reasons")
+                        .unwrap_or(None)
+                        .filter(|reasons| !reasons.is_empty())
+                        .map(|reasons| reasons.split("; ").map(str::to_string).collect::<Vec<_>>())
+                        .unwrap_or_default(),
+                    "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_else(|_| Utc::now()),
+                })
+            })
+            .collect();
+
+        Ok((comments, total))
+    }
+
+    pub async fn moderate_comments(
+        moderation: ModerateComments,
+        moderator_id: i32,
+    ) -> Result<u64, ApiError> {
+        if moderation.ids.is_empty() {
+            return Err(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Informe ao menos um comentário".to_string(),
+            });
+        }
+
+        let query = format!(
+            "UPDATE comments SET status = ?, moderated_at = ?, moderated_by = ? WHERE id IN ({})",
+            vec!["?"; moderation.ids.len()].join(", ")
+        );
+
+        match HelperMySql::get_instance() {
+            Some(instance) => {
+                let mut query_builder = sqlx::query(&query)
+                    .bind(moderation.status.as_str())
+                    .bind(Utc::now())
+                    .bind(moderator_id);
+                for id in &moderation.ids {
+                    query_builder = query_builder.bind(id);
+                }
+
+                match query_builder.execute(&instance.pool).await {
+                    Ok(result) => {
+                        // Decisões de spam e aprovação alimentam o filtro bayesiano
+                        let label = match moderation.status {
+                            CommentStatus::Spam => Some("spam"),
+                            CommentStatus::Approved => Some("ham"),
+                            _ => None,
+                        };
+                        if let Some(label) = label {
+                            if let Err(e) = Self::train_spam_filter(&moderation.ids, label).await {
+                                eprintln!("Erro ao treinar filtro de spam: {}", e);
+                            }
+                        }
+
+                        Ok(result.rows_affected())
+                    }
+                    Err(err) => Err(ApiError {
+                        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: format!("Erro ao moderar comentários: {}", err),
+                    }),
+                }
+            }
+            None => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Database not initialized".to_string(),
+            }),
+        }
+    }
+
+    pub async fn count_recent_duplicates(
+        content: &str,
+        hours: i64,
+        exclude_id: Option<i32>,
+    ) -> Result<i64, sqlx::Error> {
+        let instance = HelperMySql::get_instance().expect("Database not initialized");
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS total FROM comments WHERE content = ? AND created_at > ? AND id <> ?",
+        )
+        .bind(content)
+        .bind(Utc::now() - Duration::hours(hours))
+        .bind(exclude_id.unwrap_or_default())
+        .fetch_one(&instance.pool)
+        .await?;
+        Ok(row.try_get("total").unwrap_or_default())
+    }
+
+    pub async fn count_recent_by_user(
+        user_id: i32,
+        minutes: i64,
+        exclude_id: Option<i32>,
+    ) -> Result<i64, sqlx::Error> {
+        let instance = HelperMySql::get_instance().expect("Database not initialized");
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS total FROM comments WHERE user_id = ? AND created_at > ? AND id <> ?",
+        )
+        .bind(user_id)
+        .bind(Utc::now() - Duration::minutes(minutes))
+        .bind(exclude_id.unwrap_or_default())
+        .fetch_one(&instance.pool)
+        .await?;
+        Ok(row.try_get("total").unwrap_or_default())
+    }
+
+    pub async fn select_spam_corpus() -> Result<(i64, i64), sqlx::Error> {
+        let rows =
+            HelperMySql::execute_select("SELECT label, documents FROM comment_spam_corpus").await?;
+        let documents = |label: &str| {
+            rows.iter()
+                .find(|row| row.try_get::<String, _>("label").ok().as_deref() == Some(label))
+                .and_then(|row| row.try_get::<i32, _>("documents").ok())
+                .unwrap_or_default() as i64
+        };
+        Ok((documents("spam"), documents("ham")))
+    }
+
+    pub async fn select_spam_token_counts(
+        tokens: &[String],
+    ) -> Result<Vec<(String, i64, i64)>, sqlx::Error> {
+        if tokens.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let query = format!(
+            "SELECT token, spam, ham FROM comment_spam_tokens WHERE token IN ({})",
+            vec!["?"; tokens.len()].join(", ")
+        );
+        let rows = HelperMySql::execute_query_with_params(&query, tokens.to_vec()).await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.try_get::<String, _>("token").unwrap_or_default(),
+                    row.try_get::<i32, _>("spam").unwrap_or_default() as i64,
+                    row.try_get::<i32, _>("ham").unwrap_or_default() as i64,
+                )
+            })
+            .collect())
+    }
+
+    // `label` é "spam" ou "ham"; comentários já treinados com outro rótulo são corrigidos
+    async fn train_spam_filter(comment_ids: &[i32], label: &str) -> Result<(), sqlx::Error> {
+        for comment_id in comment_ids {
+            let rows = HelperMySql::execute_query_with_params(
+                "SELECT content, trained_as FROM comments WHERE id = ?",
+                vec![*comment_id],
+            )
+            .await?;
+            let Some(row) = rows.first() else {
+                continue;
+            };
+
+            let trained_as = row
+                .try_get::<Option<String>, _>("trained_as")
+                .unwrap_or(None);
+            if trained_as.as_deref() == Some(label) {
+                continue;
+            }
+
+            let content: String = row.try_get("content").unwrap_or_default();
+            let tokens = ServicesCommentSpam::tokenize(&content);
+
+            if let Some(previous) = trained_as.as_deref() {
+                Self::adjust_spam_counts(&tokens, previous, -1).await?;
+            }
+            Self::adjust_spam_counts(&tokens, label, 1).await?;
+
+            HelperMySql::execute_with_params(
+                "UPDATE comments SET trained_as = ? WHERE id = ?",
+                vec![label.to_string(), comment_id.to_string()],
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn adjust_spam_counts(
+        tokens: &[String],
+        label: &str,
+        delta: i32,
+    ) -> Result<(), sqlx::Error> {
+        let column = if label == "spam" { "spam" } else { "ham" };
+
+        HelperMySql::execute_with_params(
+            "UPDATE comment_spam_corpus SET documents = GREATEST(documents + ?, 0) WHERE label = ?",
+            vec![delta.to_string(), column.to_string()],
+        )
+        .await?;
+
+        if tokens.is_empty() {
+            return Ok(());
+        }
+
+        if delta < 0 {
+            let query = format!(
+                "UPDATE comment_spam_tokens SET {column} = GREATEST({column} - 1, 0) WHERE token IN ({})",
+                vec!["?"; tokens.len()].join(", "),
+                column = column
+            );
+            HelperMySql::execute_with_params(&query, tokens.to_vec()).await?;
+            return Ok(());
+        }
+
+        let query = format!(
+            "INSERT INTO comment_spam_tokens (token, {column}) VALUES {} \
+             ON DUPLICATE KEY UPDATE {column} = {column} + 1",
+            vec!["(?, 1)"; tokens.len()].join(", "),
+            column = column
+        );
+        HelperMySql::execute_with_params(&query, tokens.to_vec()).await?;
+
+        Ok(())
+    }
+
+    // Marca a reação no comentário se ainda não existir e desmarca caso contrário
+    pub async fn toggle_reaction(
+        comment_id: i32,
+        user_id: i32,
+        reaction: ReactionType,
+    ) -> Result<(bool, i64), ApiError> {
+        let error = |err: sqlx::Error| ApiError {
+            status_code: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Erro ao registrar reação: {}", err),
+        };
+
+        let removed = HelperMySql::execute_with_params(
+            "DELETE FROM comment_reactions WHERE comment_id = ? AND user_id = ? AND reaction = ?",
+            vec![
+                comment_id.to_string(),
+                user_id.to_string(),
+                reaction.as_str().to_string(),
+            ],
+        )
+        .await
+        .map_err(error)?;
+
+        let reacted = if removed.rows_affected() > 0 {
+            false
+        } else {
+            let inserted = HelperMySql::execute_with_params(
+                r#"
+                INSERT INTO comment_reactions (comment_id, user_id, reaction)
+                SELECT c.id, ?, ? FROM comments c
+                WHERE c.id = ? AND c.is_deleted = 0 AND c.status = 'approved'
+                "#,
+                vec![
+                    user_id.to_string(),
+                    reaction.as_str().to_string(),
+                    comment_id.to_string(),
+                ],
+            )
+            .await
+            .map_err(error)?;
+
+            if inserted.rows_affected() == 0 {
+                return Err(ApiError {
+                    status_code: StatusCode::NOT_FOUND,
+                    message: "Comentário não encontrado".to_string(),
+                });
+            }
+            true
+        };
+
+        let total = HelperMySql::execute_query_with_params(
+            "SELECT COUNT(*) AS total FROM comment_reactions WHERE comment_id = ?",
+            vec![comment_id],
+        )
+        .await
+        .map_err(error)?
+        .first()
+        .and_then(|row| row.try_get::<i64, _>("total").ok())
+        .unwrap_or_default();
+
+        Ok((reacted, total))
+    }
+
+    // Uma página de comentários visíveis, paginada no banco pela chave de ordenação e pelo id
+    async fn select_comment_page(
+        post_id: i32,
+        filters: &CommentListQuery,
+        roots_only: bool,
+    ) -> Result<(Vec<MySqlRow>, Option<String>), ApiError> {
+        let sort = filters.sort.unwrap_or_default();
+        let (order, comparison) = sort.direction();
+        let limit = filters.limit();
+
+        let mut conditions = vec!["c.id IN (SELECT id FROM visible)".to_string()];
+        let mut params = vec![post_id as i64];
+        if roots_only {
+            conditions.push("c.parent_id IS NULL".to_string());
+        }
+        if let Some((key, id)) = filters.cursor()? {
+            conditions.push(format!(
+                "({}, c.id) {} (?, ?)",
+                sort.key_expression(),
+                comparison
+            ));
+            params.push(key);
+            params.push(id as i64);
+        }
+
+        // O LIMIT é um inteiro já validado; o comentário a mais indica se há próxima página
+        let query = format!(
+            r#"
+            WITH RECURSIVE {}
+            SELECT {}, {} AS sort_key
+            FROM comments c
+            LEFT JOIN users u ON c.user_id = u.id
+            WHERE {}
+            ORDER BY sort_key {}, c.id {}
+            LIMIT {}
+            "#,
+            VISIBLE_COMMENTS,
+            COMMENT_COLUMNS,
+            sort.key_expression(),
+            conditions.join(" AND "),
+            order,
+            order,
+            limit + 1
+        );
+
+        let mut rows = HelperMySql::execute_query_with_params(&query, params)
+            .await
+            .map_err(|err| ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar comentários: {}", err),
+            })?;
+
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|row| {
+                format!(
+                    "{}_{}",
+                    row.try_get::<i64, _>("sort_key").unwrap_or_default(),
+                    row.try_get::<i32, _>("id").unwrap_or_default()
+                )
+            })
+        } else {
+            None
+        };
+
+        Ok((rows, next_cursor))
+    }
+
+    // Os comentários informados e todas as respostas visíveis abaixo deles, em ordem cronológica
+    async fn select_thread_rows(post_id: i32, root_ids: &[i32]) -> Result<Vec<MySqlRow>, ApiError> {
+        if root_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let placeholders = vec!["?"; root_ids.len()].join(", ");
+        let query = format!(
+            r#"
+            WITH RECURSIVE {},
+            thread AS (
+                SELECT id FROM comments WHERE post_id = ? AND id IN ({})
+                UNION ALL
+                SELECT c.id FROM comments c INNER JOIN thread t ON c.parent_id = t.id
+            )
+            SELECT {}
+            FROM comments c
+            LEFT JOIN users u ON c.user_id = u.id
+            WHERE c.id IN (SELECT id FROM thread) AND c.id IN (SELECT id FROM visible)
+            ORDER BY c.created_at, c.id
+            "#,
+            VISIBLE_COMMENTS, placeholders, COMMENT_COLUMNS
+        );
+
+        let mut params = vec![post_id, post_id];
+        params.extend_from_slice(root_ids);
+
+        HelperMySql::execute_query_with_params(&query, params)
+            .await
+            .map_err(|err| ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar comentários: {}", err),
+            })
+    }
+
+    pub async fn select_comment_by_post(
+        post_id: i32,
+        filters: &CommentListQuery,
+    ) -> Result<serde_json::Value, ApiError> {
+        let count_query = format!(
+            r#"
+            WITH RECURSIVE {}
+            SELECT
+                COUNT(*) AS total,
+                CAST(COALESCE(SUM(parent_id IS NULL), 0) AS SIGNED) AS total_threads
+            FROM visible
+            "#,
+            VISIBLE_COMMENTS
+        );
+        let (total, total_threads) =
+            match HelperMySql::execute_query_with_params(&count_query, vec![post_id]).await {
+                Ok(rows) => rows
+                    .first()
+                    .map(|row| {
+                        (
+                            row.try_get::<i64, _>("total").unwrap_or_default(),
+                            row.try_get::<i64, _>("total_threads").unwrap_or_default(),
+                        )
+                    })
+                    .unwrap_or_default(),
+                Err(err) => {
+                    return Err(ApiError {
+                        status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: format!("Erro ao buscar comentários: {}", err),
+                    })
+                }
+            };
+
+        let sort = filters.sort.unwrap_or_default();
+
+        if !filters.tree.unwrap_or(false) {
+            let (rows, next_cursor) = Self::select_comment_page(post_id, filters, false).await?;
+            let page: Vec<serde_json::Value> = rows.iter().map(comment_row_to_json).collect();
+            return Ok(json!({
+                "comments": page,
+                "total": total,
+                "sort": sort,
+                "next_cursor": next_cursor,
+                "has_more": next_cursor.is_some(),
+            }));
+        }
+
+        let max_depth = filters.max_depth();
+
+        // Sem `root_id`, a paginação vale para as conversas (comentários raiz);
+        // as respostas de cada conversa seguem em ordem cronológica
+        let (roots, thread_rows, next_cursor) = match filters.root_id {
+            Some(root_id) => {
+                let rows = Self::select_thread_rows(post_id, &[root_id]).await?;
+                let root = rows
+                    .iter()
+                    .find(|row| row.try_get::<i32, _>("id").ok() == Some(root_id))
+                    .map(comment_row_to_json)
+                    .ok_or(ApiError {
+                        status_code: StatusCode::NOT_FOUND,
+                        message: "Comentário não encontrado".to_string(),
+                    })?;
+                (vec![root], rows, None)
+            }
+            None => {
+                let (rows, next_cursor) = Self::select_comment_page(post_id, filters, true).await?;
+                let root_ids: Vec<i32> = rows
+                    .iter()
+                    .filter_map(|row| row.try_get::<i32, _>("id").ok())
+                    .collect();
+                let thread_rows = Self::select_thread_rows(post_id, &root_ids).await?;
+                (
+                    rows.iter().map(comment_row_to_json).collect(),
+                    thread_rows,
+                    next_cursor,
+                )
+            }
+        };
+
+        let root_ids: HashSet<i64> = roots
+            .iter()
+            .filter_map(|comment| comment["id"].as_i64())
+            .collect();
+        let replies: Vec<serde_json::Value> = thread_rows
+            .iter()
+            .map(comment_row_to_json)
+            .filter(|comment| {
+                comment["id"]
+                    .as_i64()
+                    .is_some_and(|id| !root_ids.contains(&id))
+            })
+            .collect();
+
+        let mut children: HashMap<Option<i32>, Vec<&serde_json::Value>> = HashMap::new();
+        children.insert(None, roots.iter().collect());
+        for reply in &replies {
+            let parent_id = reply["parent_id"]
+                .as_i64()
+                .map(|parent_id| parent_id as i32);
+            children.entry(parent_id).or_default().push(reply);
+        }
+
+        Ok(json!({
+            "comments": build_comment_tree(&children, None, 0, max_depth),
+            "total": total,
+            "total_threads": total_threads,
+            "max_depth": max_depth,
+            "sort": sort,
+            "next_cursor": next_cursor,
+            "has_more": next_cursor.is_some(),
+        }))
+    }
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -8,12 +8,117 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
-use sqlx::{prelude::FromRow, Row};
+use similar::TextDiff;
+use sqlx::{mysql::MySqlRow, prelude::FromRow, Row};
 
 use crate::helpers::{db::helpers_mysql::HelperMySql, response::helpers_response::HelpersResponse};
+use crate::mvc::services::post::markdown::services_post_markdown::ServicesPostMarkdown;
+use crate::mvc::services::post::related::services_post_related::{
+    ServicesPostRelated, MAX_RELATED,
+};
+use crate::mvc::services::post::search_index::services_post_search_index::ServicesPostSearchIndex;
 
 pub struct ModelPost;
 
+// Posts agendados ficam visíveis assim que a data passa, mesmo antes da
+// tarefa em segundo plano atualizar o status
+pub const PUBLISHED_CONDITION: &str = "p.is_active = 1 \
+    AND p.status IN ('published', 'scheduled') \
+    AND p.publication_date <= NOW()";
+
+const POST_SELECT: &str = r#"
+    SELECT
+        p.*, p.id AS post_id, a.name AS author_name, c.name AS category_name,
+        (SELECT GROUP_CONCAT(t.name ORDER BY t.name SEPARATOR ',')
+            FROM post_tags pt
+            INNER JOIN tags t ON t.id = pt.tag_id
+            WHERE pt.post_id = p.id) AS tag_names,
+        (SELECT COUNT(*) FROM post_reactions r WHERE r.post_id = p.id AND r.reaction = 'like') AS reactions_like,
+        (SELECT COUNT(*) FROM post_reactions r WHERE r.post_id = p.id AND r.reaction = 'love') AS reactions_love,
+        (SELECT COUNT(*) FROM post_reactions r WHERE r.post_id = p.id AND r.reaction = 'laugh') AS reactions_laugh,
+        (SELECT COUNT(*) FROM post_reactions r WHERE r.post_id = p.id AND r.reaction = 'insightful') AS reactions_insightful,
+        (SELECT COUNT(*) FROM post_reactions r WHERE r.post_id = p.id AND r.reaction = 'celebrate') AS reactions_celebrate,
+        (SELECT COUNT(*) FROM comments cm
+            WHERE cm.post_id = p.id AND cm.is_deleted = 0 AND cm.status = 'approved') AS comment_count
+    FROM
+        posts p
+    LEFT JOIN authors a ON p.author_id = a.id
+    LEFT JOIN categories c ON p.category_id = c.id
+"#;
+
+// Rascunhos sem data de publicação entram na ordenação com uma data mínima
+const UNDATED_POST: &str = "1000-01-01T00:00:00";
+const CURSOR_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+// Tags do post no momento da revisão; string vazia indica um post sem tags
+const REVISION_TAG_NAMES: &str =
+    "COALESCE((SELECT GROUP_CONCAT(t.name ORDER BY t.name SEPARATOR ',') \
+        FROM post_tags pt INNER JOIN tags t ON t.id = pt.tag_id \
+        WHERE pt.post_id = p.id), '')";
+
+// Subconsulta com a categoria informada e todas as suas descendentes
+const CATEGORY_DESCENDANTS: &str = "WITH RECURSIVE descendants AS ( \
+        SELECT id FROM categories WHERE id = ? \
+        UNION ALL \
+        SELECT c.id FROM categories c INNER JOIN descendants d ON c.parent_id = d.id \
+    ) SELECT id FROM descendants";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    Draft,
+    Scheduled,
+    Published,
+    Archived,
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Published => "published",
+            PostStatus::Archived => "archived",
+        }
+    }
+}
+
+// Os contadores de POST_SELECT seguem esta mesma lista
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReactionType {
+    Like,
+    Love,
+    Laugh,
+    Insightful,
+    Celebrate,
+}
+
+impl ReactionType {
+    pub const ALL: [ReactionType; 5] = [
+        ReactionType::Like,
+        ReactionType::Love,
+        ReactionType::Laugh,
+        ReactionType::Insightful,
+        ReactionType::Celebrate,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionType::Like => "like",
+            ReactionType::Love => "love",
+            ReactionType::Laugh => "laugh",
+            ReactionType::Insightful => "insightful",
+            ReactionType::Celebrate => "celebrate",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReactionRequest {
+    pub reaction: ReactionType,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostRequestModel {
     pub post: PostRequestItem,
@@ -43,6 +148,9 @@ pub struct PostRequestItem {
     pub post_image_url: Option<String>,
     pub content: String,
     pub slug: String,
+    pub status: Option<PostStatus>,
+    pub publication_date: Option<NaiveDateTime>,
+    pub tags: Option<Vec<String>>,
 }
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Post {
@@ -77,6 +185,67 @@ pub struct CreateCategory {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateCategoryItem {
     pub name: String,
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditAuthor {
+    pub author: EditAuthorItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditAuthorItem {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteAuthor {
+    pub author: DeleteAuthorItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteAuthorItem {
+    pub id: i32,
+    pub reassign_to: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditCategory {
+    pub category: EditCategoryItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditCategoryItem {
+    pub id: i32,
+    pub name: String,
+    // Ausente mantém a categoria pai atual; null move a categoria para a raiz
+    #[serde(
+        default,
+        deserialize_with = "present_field",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parent_id: Option<Option<i32>>,
+}
+
+// Diferencia um campo ausente (None) de um campo enviado como null (Some(None))
+fn present_field<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteCategory {
+    pub category: DeleteCategoryItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteCategoryItem {
+    pub id: i32,
+    pub reassign_to: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -94,6 +263,10 @@ pub struct EditPostItem {
     pub post_image_url: Option<String>,
     pub content: String,
     pub slug: String,
+    pub status: Option<PostStatus>,
+    pub publication_date: Option<NaiveDateTime>,
+    pub version: Option<i32>,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, FromRow, Serialize)]
@@ -106,6 +279,215 @@ pub struct Author {
 pub struct CategoryReq {
     pub id: i32,
     pub name: String,
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTag {
+    pub tag: CreateTagItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTagItem {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditTag {
+    pub tag: EditTagItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditTagItem {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteTag {
+    pub tag: DeleteTagItem,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteTagItem {
+    pub id: i32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PostListQuery {
+    pub cursor: Option<String>,
+    pub before: Option<String>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    pub category_id: Option<i32>,
+    pub author_id: Option<i32>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub status: Option<PostStatus>,
+    pub tag: Option<String>,
+}
+
+impl PostListQuery {
+    pub const DEFAULT_PER_PAGE: u32 = 10;
+    pub const MAX_PER_PAGE: u32 = 100;
+
+    pub fn per_page(&self) -> u32 {
+        self.per_page
+            .unwrap_or(Self::DEFAULT_PER_PAGE)
+            .clamp(1, Self::MAX_PER_PAGE)
+    }
+
+    pub fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn uses_cursor(&self) -> bool {
+        [&self.cursor, &self.before]
+            .iter()
+            .any(|cursor| cursor.as_deref().is_some_and(|cursor| !cursor.is_empty()))
+    }
+
+    // O cursor é "<chave>_<id>", com a chave sendo a data ou o título do último post da página
+    pub fn cursor(&self) -> Result<Option<(String, i32)>, ApiError> {
+        Self::parse_cursor(self.cursor.as_deref())
+    }
+
+    // Mesmo formato, mas aponta para o primeiro post da página e volta uma página
+    pub fn before(&self) -> Result<Option<(String, i32)>, ApiError> {
+        Self::parse_cursor(self.before.as_deref())
+    }
+
+    fn parse_cursor(cursor: Option<&str>) -> Result<Option<(String, i32)>, ApiError> {
+        let Some(cursor) = cursor.filter(|cursor| !cursor.is_empty()) else {
+            return Ok(None);
+        };
+
+        cursor
+            .rsplit_once('_')
+            .and_then(|(key, id)| Some((key.to_string(), id.parse::<i32>().ok()?)))
+            .map(Some)
+            .ok_or(ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Cursor inválido".to_string(),
+            })
+    }
+}
+
+#[derive(Debug)]
+pub struct PostListPage {
+    pub posts: Vec<serde_json::Value>,
+    pub total: i64,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct PostRevision {
+    pub id: i32,
+    pub post_id: i32,
+    pub user_id: Option<i32>,
+    pub author_id: i32,
+    pub category_id: i32,
+    pub title: String,
+    pub description: String,
+    pub post_image_url: Option<String>,
+    pub content: String,
+    pub slug: String,
+    pub status: String,
+    pub publication_date: Option<NaiveDateTime>,
+    pub tag_names: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevisionDiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PostSearchQuery {
+    pub q: Option<String>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    pub category_id: Option<i32>,
+    pub tag: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl PostSearchQuery {
+    pub fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u32 {
+        self.per_page
+            .unwrap_or(PostListQuery::DEFAULT_PER_PAGE)
+            .clamp(1, PostListQuery::MAX_PER_PAGE)
+    }
+
+    pub fn offset(&self) -> u64 {
+        (self.page() as u64 - 1) * self.per_page() as u64
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PostIndexSearchQuery {
+    pub q: Option<String>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    pub category_id: Option<i32>,
+    pub author_id: Option<i32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RelatedPostsQuery {
+    pub limit: Option<u32>,
+}
+
+impl RelatedPostsQuery {
+    pub const DEFAULT_LIMIT: u32 = 5;
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, MAX_RELATED as u32)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PopularPostsQuery {
+    pub window: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl PopularPostsQuery {
+    pub const MAX_WINDOW_DAYS: u32 = 365;
+
+    // Aceita "24h", "7d" ou "4w"; as visualizações são agregadas por dia
+    pub fn days(&self) -> Option<u32> {
+        let window = self.window.as_deref().unwrap_or("7d").trim();
+        if window.len() < 2 {
+            return None;
+        }
+        let (amount, unit) = window.split_at(window.len() - 1);
+        let amount = amount.parse::<u32>().ok().filter(|amount| *amount > 0)?;
+
+        let days = match unit {
+            "h" => amount.div_ceil(24),
+            "d" => amount,
+            "w" => amount.saturating_mul(7),
+            _ => return None,
+        };
+        Some(days.min(Self::MAX_WINDOW_DAYS))
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(PostListQuery::DEFAULT_PER_PAGE)
+            .clamp(1, PostListQuery::MAX_PER_PAGE)
+    }
 }
 
 pub struct ApiError {
@@ -126,63 +508,423 @@ impl IntoResponse for ApiError {
     }
 }
 
+// Sumário e tempo de leitura gravados no save; posts antigos ainda não preenchidos
+// por backfill_rendered_content são calculados na leitura
+fn attach_reading_info(post: &mut serde_json::Value, row: &MySqlRow) {
+    let stored = (
+        row.try_get::<Option<String>, _>("toc").unwrap_or(None),
+        row.try_get::<Option<i32>, _>("word_count").unwrap_or(None),
+        row.try_get::<Option<i32>, _>("reading_time_minutes")
+            .unwrap_or(None),
+    );
+
+    let (toc, word_count, reading_time) = match stored {
+        (Some(toc), Some(word_count), Some(reading_time)) => (
+            serde_json::from_str(&toc).unwrap_or_else(|_| json!([])),
+            word_count as usize,
+            reading_time as usize,
+        ),
+        _ => {
+            let content = row.try_get::<String, _>("content").unwrap_or_default();
+            let rendered = ServicesPostMarkdown::render_document(&content);
+            (
+                json!(rendered.toc),
+                rendered.word_count,
+                rendered.reading_time_minutes(),
+            )
+        }
+    };
+
+    post["toc"] = toc;
+    post["word_count"] = json!(word_count);
+    post["reading_time_minutes"] = json!(reading_time);
+}
+
+fn reaction_counts(row: &MySqlRow) -> serde_json::Value {
+    let counts: serde_json::Map<String, serde_json::Value> = ReactionType::ALL
+        .iter()
+        .map(|reaction| {
+            let column = format!("reactions_{}", reaction.as_str());
+            (
+                reaction.as_str().to_string(),
+                json!(row.try_get::<i64, _>(column.as_str()).unwrap_or_default()),
+            )
+        })
+        .collect();
+    serde_json::Value::Object(counts)
+}
+
+fn post_row_to_json(row: &MySqlRow) -> serde_json::Value {
+    let content = row.try_get::<String, _>("content").unwrap_or_default();
+
+    // Posts anteriores ao cache de HTML só são renderizados na leitura até o
+    // backfill_rendered_content da inicialização gravar o resultado
+    let content_html = row
+        .try_get::<Option<String>, _>("content_html")
+        .unwrap_or(None)
+        .unwrap_or_else(|| ServicesPostMarkdown::render(&content));
+
+    json!({
+        "id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
+        "author_id": row.try_get::<i32, _>("author_id").unwrap_or_default(),
+        "author_name": row.try_get::<String, _>("author_name").unwrap_or_default(),
+        "category_id": row.try_get::<i32, _>("category_id").unwrap_or_default(),
+        "category_name": row.try_get::<String, _>("category_name").unwrap_or_default(),
+        "title": row.try_get::<String, _>("title").unwrap_or_default(),
+        "description": row.try_get::<String, _>("description").unwrap_or_default(),
+        "post_image_url": row.try_get::<Option<String>, _>("post_image_url").unwrap_or(None),
+        "content": content,
+        "content_markdown": content,
+        "content_html": content_html,
+        "slug": row.try_get::<String, _>("slug").unwrap_or_default(),
+        "is_active": row.try_get::<i32, _>("is_active").unwrap_or_default(),
+        "status": row.try_get::<String, _>("status").unwrap_or_default(),
+        "version": row.try_get::<i32, _>("version").unwrap_or_default(),
+        "publication_date": row.try_get::<Option<NaiveDateTime>, _>("publication_date").unwrap_or(None),
+        "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_default(),
+        "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_default(),
+        "tags": row
+            .try_get::<Option<String>, _>("tag_names")
+            .unwrap_or(None)
+            .map(|names| names.split(',').map(str::to_string).collect::<Vec<_>>())
+            .unwrap_or_default(),
+        "reactions": reaction_counts(row),
+        "comment_count": row.try_get::<i64, _>("comment_count").unwrap_or_default(),
+    })
+}
+
+fn post_cursor(row: &MySqlRow, sort: &str) -> String {
+    let key = match sort {
+        "title" => row.try_get::<String, _>("title").unwrap_or_default(),
+        "created_at" => row
+            .try_get::<DateTime<Utc>, _>("created_at")
+            .unwrap_or_default()
+            .format(CURSOR_DATE_FORMAT)
+            .to_string(),
+        _ => row
+            .try_get::<Option<NaiveDateTime>, _>("publication_date")
+            .unwrap_or(None)
+            .map(|date| date.format(CURSOR_DATE_FORMAT).to_string())
+            .unwrap_or_else(|| UNDATED_POST.to_string()),
+    };
+
+    format!(
+        "{}_{}",
+        key,
+        row.try_get::<i32, _>("post_id").unwrap_or_default()
+    )
+}
+
+fn build_category_tree(
+    categories: &[CategoryReq],
+    parent_id: Option<i32>,
+) -> Vec<serde_json::Value> {
+    categories
+        .iter()
+        .filter(|category| category.parent_id == parent_id)
+        .map(|category| {
+            json!({
+                "id": category.id,
+                "name": category.name,
+                "parent_id": category.parent_id,
+                "children": build_category_tree(categories, Some(category.id)),
+            })
+        })
+        .collect()
+}
+
+// Vírgulas são removidas porque o GROUP_CONCAT usa vírgula como separador
+fn normalize_tag_names(names: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = vec![];
+
+    for name in names {
+        let name = name.replace(',', " ").trim().to_string();
+        if name.is_empty() {
+            continue;
+        }
+        if normalized.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            continue;
+        }
+        normalized.push(name);
+    }
+
+    normalized
+}
+
+fn is_duplicate_entry(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|db_err| db_err.code())
+        .is_some_and(|code| code == "23000")
+}
+
 impl ModelPost {
-    pub async fn select_post() -> Result<Vec<serde_json::Value>, sqlx::Error> {
-        let query = "    SELECT 
-                p.id AS post_id, p.author_id, a.id AS author_id, a.name AS author_name,
-                p.category_id, c.id AS category_id, c.name AS category_name,
-                p.*
-            FROM 
-                    posts p
-                LEFT JOIN authors a ON p.author_id = a.id
-                LEFT JOIN categories c ON p.category_id = c.id;
-          "; // Ajuste para a sua tabela
-        match HelperMySql::execute_select(query).await {
-            Ok(rows) => {
-                // Convertendo linhas para JSON
-                let posts: Vec<serde_json::Value> = rows
-                    .into_iter()
-                    .map(|row| {
-                        json!({
-                            "id": row.try_get::<i32, _>("id").unwrap_or_default(),
-                            "author_id": row.try_get::<i32, _>("author_id").unwrap_or_default(),
-                            "author_name": row.try_get::<String, _>("author_name").unwrap_or_default(),
-                            "category_id": row.try_get::<i32, _>("category_id").unwrap_or_default(),
-                            "category_name": row.try_get::<String, _>("category_name").unwrap_or_default(),
-                            "title": row.try_get::<String, _>("title").unwrap_or_default(),
-                            "description": row.try_get::<String, _>("description").unwrap_or_default(),
-                            "post_image_url": row.try_get::<Option<String>, _>("post_image_url").unwrap_or(None),
-                            "content": row.try_get::<String, _>("content").unwrap_or_default(),
-                            "slug": row.try_get::<String, _>("slug").unwrap_or_default(),
-                            "is_active": row.try_get::<i32, _>("is_active").unwrap_or_default(),
-                            "publication_date": row.try_get::<NaiveDateTime, _>("publication_date").unwrap_or_default(),
-                            "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_default(),
-                            "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_default(),
-                        })
+    pub async fn select_post(
+        filters: &PostListQuery,
+        only_published: bool,
+    ) -> Result<PostListPage, ApiError> {
+        let sort = filters.sort.as_deref().unwrap_or("publication_date");
+        let sort_column = match sort {
+            "publication_date" => "IFNULL(p.publication_date, TIMESTAMP('1000-01-01'))",
+            "created_at" => "p.created_at",
+            "title" => "p.title",
+            _ => {
+                return Err(ApiError {
+                    status_code: StatusCode::BAD_REQUEST,
+                    message: "Parâmetro sort inválido".to_string(),
+                })
+            }
+        };
+
+        let ascending = match filters.order.as_deref().unwrap_or("desc") {
+            "asc" => true,
+            "desc" => false,
+            _ => {
+                return Err(ApiError {
+                    status_code: StatusCode::BAD_REQUEST,
+                    message: "Parâmetro order inválido".to_string(),
+                })
+            }
+        };
+
+        let (cursor, backwards) = match (filters.cursor()?, filters.before()?) {
+            (Some(_), Some(_)) => {
+                return Err(ApiError {
+                    status_code: StatusCode::BAD_REQUEST,
+                    message: "Use cursor ou before, não os dois".to_string(),
+                })
+            }
+            (Some(cursor), None) => (Some(cursor), false),
+            (None, Some(before)) => (Some(before), true),
+            (None, None) => (None, false),
+        };
+
+        // Voltando uma página, a busca anda no sentido contrário e o resultado é invertido depois
+        let (order, comparison) = if ascending != backwards {
+            ("ASC", ">")
+        } else {
+            ("DESC", "<")
+        };
+
+        let cursor = match cursor {
+            Some((key, id)) if sort == "title" => Some((key, id)),
+            Some((key, id)) => match NaiveDateTime::parse_from_str(&key, CURSOR_DATE_FORMAT) {
+                Ok(date) => Some((date.format("%Y-%m-%d %H:%M:%S").to_string(), id)),
+                Err(_) => {
+                    return Err(ApiError {
+                        status_code: StatusCode::BAD_REQUEST,
+                        message: "Cursor inválido".to_string(),
                     })
-                    .collect();
-                Ok(posts)
+                }
+            },
+            None => None,
+        };
+
+        let mut conditions: Vec<&str> = vec![];
+        let mut params: Vec<String> = vec![];
+
+        if only_published {
+            conditions.push(PUBLISHED_CONDITION);
+        } else {
+            conditions.push("p.is_active = 1");
+
+            if let Some(status) = filters.status {
+                conditions.push("p.status = ?");
+                params.push(status.as_str().to_string());
+            }
+        }
+
+        let category_condition = format!("p.category_id IN ({})", CATEGORY_DESCENDANTS);
+        if let Some(category_id) = filters.category_id {
+            conditions.push(&category_condition);
+            params.push(category_id.to_string());
+        }
+
+        if let Some(author_id) = filters.author_id {
+            conditions.push("p.author_id = ?");
+            params.push(author_id.to_string());
+        }
+
+        if let Some(tag) = &filters.tag {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM post_tags pt INNER JOIN tags t ON t.id = pt.tag_id \
+                    WHERE pt.post_id = p.id AND t.name = ?)",
+            );
+            params.push(tag.trim().to_string());
+        }
+
+        let where_clause = format!("WHERE {}", conditions.join(" AND "));
+
+        let count_query = format!("SELECT COUNT(*) AS total FROM posts p {}", where_clause);
+        let total = match HelperMySql::execute_query_with_params(&count_query, params.clone()).await
+        {
+            Ok(rows) => rows
+                .first()
+                .and_then(|row| row.try_get::<i64, _>("total").ok())
+                .unwrap_or_default(),
+            Err(err) => {
+                return Err(ApiError {
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: format!("Erro ao buscar posts: {}", err),
+                })
+            }
+        };
+
+        // Paginação por chave: continua a partir de (chave, id) do último post entregue
+        let cursor_condition = format!("({}, p.id) {} (?, ?)", sort_column, comparison);
+        let seeking = cursor.is_some();
+        if let Some((key, id)) = cursor {
+            conditions.push(&cursor_condition);
+            params.push(key);
+            params.push(id.to_string());
+        }
+
+        // LIMIT e OFFSET são inteiros já validados, por isso entram direto na query;
+        // o post a mais indica se existe outra página no sentido da busca
+        let per_page = filters.per_page() as usize;
+        let offset = if !seeking {
+            (filters.page() as u64 - 1) * per_page as u64
+        } else {
+            0
+        };
+        let query = format!(
+            r#"
+            {}
+            WHERE {}
+            ORDER BY {} {}, p.id {}
+            LIMIT {} OFFSET {}
+            "#,
+            POST_SELECT,
+            conditions.join(" AND "),
+            sort_column,
+            order,
+            order,
+            per_page + 1,
+            offset
+        );
+
+        match HelperMySql::execute_query_with_params(&query, params).await {
+            Ok(rows) => {
+                let has_more = rows.len() > per_page;
+                let mut rows: Vec<MySqlRow> = rows.into_iter().take(per_page).collect();
+                if backwards {
+                    rows.reverse();
+                }
+
+                // Quem volta uma página sempre tem a página de onde veio logo adiante
+                let (has_next, has_prev) = if backwards {
+                    (true, has_more)
+                } else {
+                    (has_more, seeking || offset > 0)
+                };
+
+                Ok(PostListPage {
+                    posts: rows.iter().map(post_row_to_json).collect(),
+                    total,
+                    next_cursor: rows
+                        .last()
+                        .filter(|_| has_next)
+                        .map(|row| post_cursor(row, sort)),
+                    prev_cursor: rows
+                        .first()
+                        .filter(|_| has_prev)
+                        .map(|row| post_cursor(row, sort)),
+                })
             }
-            Err(err) => Err(err),
+            Err(err) => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar posts: {}", err),
+            }),
+        }
+    }
+
+    pub async fn search_posts(
+        search: &PostSearchQuery,
+        boolean_query: &str,
+    ) -> Result<(Vec<serde_json::Value>, i64), ApiError> {
+        let mut conditions: Vec<&str> = vec![
+            PUBLISHED_CONDITION,
+            "MATCH(p.title, p.description, p.content) AGAINST (? IN BOOLEAN MODE)",
+        ];
+        let mut params: Vec<String> = vec![boolean_query.to_string()];
+
+        let category_condition = format!("p.category_id IN ({})", CATEGORY_DESCENDANTS);
+        if let Some(category_id) = search.category_id {
+            conditions.push(&category_condition);
+            params.push(category_id.to_string());
+        }
+
+        if let Some(tag) = &search.tag {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM post_tags pt INNER JOIN tags t ON t.id = pt.tag_id \
+                    WHERE pt.post_id = p.id AND t.name = ?)",
+            );
+            params.push(tag.trim().to_string());
+        }
+
+        if let Some(from) = search.from {
+            conditions.push("p.publication_date >= ?");
+            params.push(from.format("%Y-%m-%d 00:00:00").to_string());
+        }
+
+        if let Some(to) = search.to {
+            conditions.push("p.publication_date <= ?");
+            params.push(to.format("%Y-%m-%d 23:59:59").to_string());
+        }
+
+        let where_clause = format!("WHERE {}", conditions.join(" AND "));
+
+        let count_query = format!("SELECT COUNT(*) AS total FROM posts p {}", where_clause);
+        let total = match HelperMySql::execute_query_with_params(&count_query, params.clone()).await
+        {
+            Ok(rows) => rows
+                .first()
+                .and_then(|row| row.try_get::<i64, _>("total").ok())
+                .unwrap_or_default(),
+            Err(err) => {
+                return Err(ApiError {
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: format!("Erro ao pesquisar posts: {}", err),
+                })
+            }
+        };
+
+        // Ocorrências no título pesam o dobro das encontradas no corpo do post
+        let query = format!(
+            r#"
+            {}
+            {}
+            ORDER BY
+                MATCH(p.title) AGAINST (? IN BOOLEAN MODE) * 2
+                + MATCH(p.title, p.description, p.content) AGAINST (? IN BOOLEAN MODE) DESC,
+                p.publication_date DESC
+            LIMIT {} OFFSET {}
+            "#,
+            POST_SELECT,
+            where_clause,
+            search.per_page(),
+            search.offset()
+        );
+        params.push(boolean_query.to_string());
+        params.push(boolean_query.to_string());
+
+        match HelperMySql::execute_query_with_params(&query, params).await {
+            Ok(rows) => {
+                let posts: Vec<serde_json::Value> = rows.iter().map(post_row_to_json).collect();
+                Ok((posts, total))
+            }
+            Err(err) => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao pesquisar posts: {}", err),
+            }),
         }
     }
 
     pub async fn select_post_by_id(post_id: i32) -> Result<serde_json::Value, ApiError> {
-        let query = r#"
-            SELECT 
-                p.id AS post_id, p.author_id, a.id AS author_id, a.name AS author_name,
-                p.category_id, c.id AS category_id, c.name AS category_name,
-                p.title, p.description, p.publication_date, p.post_image_url, 
-                p.content, p.created_at, p.updated_at
-            FROM 
-                posts p
-            LEFT JOIN authors a ON p.author_id = a.id
-            LEFT JOIN categories c ON p.category_id = c.id;
-        "#;
+        let query = format!("{} WHERE p.id = ? AND {}", POST_SELECT, PUBLISHED_CONDITION);
 
         // Executa a consulta ao banco de dados com o ID como parâmetro
-        let params: Vec<i32> = vec![post_id.into()];
-        match HelperMySql::execute_query_with_params(query, params).await {
+        let params: Vec<i32> = vec![post_id];
+        match HelperMySql::execute_query_with_params(&query, params).await {
             Ok(rows) => {
                 // Verifica se houve retorno
                 if rows.is_empty() {
@@ -192,24 +934,8 @@ impl ModelPost {
                     });
                 }
 
-                // Mapeia os dados do primeiro resultado para JSON
-                let row = &rows[0];
-                let post = json!({
-                    "id": row.try_get::<i32, _>("id").unwrap_or_default(),
-                    "author_id": row.try_get::<i32, _>("author_id").unwrap_or_default(),
-                    "author_name": row.try_get::<String, _>("author_name").unwrap_or_default(),
-                    "category_id": row.try_get::<i32, _>("category_id").unwrap_or_default(),
-                    "category_name": row.try_get::<String, _>("category_name").unwrap_or_default(),
-                    "title": row.try_get::<String, _>("title").unwrap_or_default(),
-                    "description": row.try_get::<String, _>("description").unwrap_or_default(),
-                    "post_image_url": row.try_get::<Option<String>, _>("post_image_url").unwrap_or(None),
-                    "content": row.try_get::<String, _>("content").unwrap_or_default(),
-                    "slug": row.try_get::<String, _>("slug").unwrap_or_default(),
-                    "is_active": row.try_get::<i32, _>("is_active").unwrap_or_default(),
-                    "publication_date": row.try_get::<NaiveDateTime, _>("publication_date").unwrap_or_default(),
-                    "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_default(),
-                    "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_default(),
-                });
+                let mut post = post_row_to_json(&rows[0]);
+                attach_reading_info(&mut post, &rows[0]);
 
                 Ok(post)
             }
@@ -220,13 +946,74 @@ impl ModelPost {
         }
     }
 
-    pub async fn get_all_slugs() -> Result<Vec<String>, sqlx::Error> {
-        let query = "SELECT slug FROM  posts";
-        let rows = HelperMySql::execute_select(query).await?;
+    // Slugs em uso que podem colidir com `base` ou suas variações numeradas,
+    // incluindo os antigos que ainda redirecionam
+    pub async fn select_taken_slugs(
+        base: &str,
+        exclude_post_id: Option<i32>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = r#"
+            SELECT slug FROM posts WHERE (slug = ? OR slug LIKE ?) AND id <> ?
+            UNION
+            SELECT slug FROM post_slug_history WHERE (slug = ? OR slug LIKE ?) AND post_id <> ?
+        "#;
+
+        let pattern = format!("{}-%", base);
+        let exclude = exclude_post_id.unwrap_or_default().to_string();
+        let params = vec![
+            base.to_string(),
+            pattern.clone(),
+            exclude.clone(),
+            base.to_string(),
+            pattern,
+            exclude,
+        ];
+
+        let rows = HelperMySql::execute_query_with_params(query, params).await?;
         let slugs: Vec<String> = rows.into_iter().map(|row| row.get("slug")).collect();
         Ok(slugs)
     }
 
+    // Slugs publicados com a data da última alteração, para o sitemap
+    pub async fn get_published_slugs() -> Result<Vec<(String, DateTime<Utc>)>, sqlx::Error> {
+        let query = format!(
+            "SELECT p.slug, p.updated_at FROM posts p WHERE {} ORDER BY p.id",
+            PUBLISHED_CONDITION
+        );
+        let rows = HelperMySql::execute_select(query).await?;
+        let slugs = rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.try_get("slug").unwrap_or_default(),
+                    row.try_get("updated_at").unwrap_or_default(),
+                )
+            })
+            .collect();
+        Ok(slugs)
+    }
+
+    // Categorias e autores com posts publicados, datados pelo post mais recente
+    pub async fn get_sitemap_taxonomies(
+        column: &str,
+    ) -> Result<Vec<(i32, DateTime<Utc>)>, sqlx::Error> {
+        let query = format!(
+            "SELECT p.{0} AS id, MAX(p.updated_at) AS lastmod FROM posts p WHERE {1} GROUP BY p.{0} ORDER BY p.{0}",
+            column, PUBLISHED_CONDITION
+        );
+        let rows = HelperMySql::execute_select(query).await?;
+        let entries = rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.try_get("id").unwrap_or_default(),
+                    row.try_get("lastmod").unwrap_or_default(),
+                )
+            })
+            .collect();
+        Ok(entries)
+    }
+
     pub async fn get_all_categories() -> Result<Vec<CategoryReq>, sqlx::Error> {
         let query = "SELECT * from categories";
         let rows = HelperMySql::execute_select(query).await?;
@@ -245,13 +1032,34 @@ impl ModelPost {
         authors
     }
 
+    pub async fn select_author_by_id(author_id: i32) -> Result<serde_json::Value, ApiError> {
+        Self::select_taxonomy_page("authors", "author_id", author_id, "Autor não encontrado").await
+    }
+
+    pub async fn select_category_by_id(category_id: i32) -> Result<serde_json::Value, ApiError> {
+        Self::select_taxonomy_page(
+            "categories",
+            "category_id",
+            category_id,
+            "Categoria não encontrada",
+        )
+        .await
+    }
+
     pub async fn create_author(create_author: CreateAuthor) -> impl IntoResponse {
+        let name = create_author.author.name.trim().to_string();
+        match Self::name_in_use("authors", &name, None).await {
+            Ok(true) => return HelpersResponse::conflict("Já existe um autor com esse nome"),
+            Ok(false) => {}
+            Err(e_) => return HelpersResponse::error_with_detail("Erro ao criar autor", e_),
+        }
+
         let query = r#"
             INSERT INTO authors (name) 
             VALUES (?)
         "#;
 
-        let params = vec![create_author.author.name.to_string()];
+        let params = vec![name];
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(_) => HelpersResponse::success("Autor criado!", create_author).into_response(),
@@ -259,13 +1067,60 @@ impl ModelPost {
         }
     }
 
-    pub async fn create_category(create_category: CreateCategory) -> impl IntoResponse {
-        let query = r#"
-        INSERT INTO categories (name)
-        VALUES (?)
-        "#;
+    pub async fn edit_author(edit_author: EditAuthor) -> impl IntoResponse {
+        let name = edit_author.author.name.trim().to_string();
+        match Self::name_in_use("authors", &name, Some(edit_author.author.id)).await {
+            Ok(true) => return HelpersResponse::conflict("Já existe um autor com esse nome"),
+            Ok(false) => {}
+            Err(e_) => return HelpersResponse::error_with_detail("Erro ao editar autor", e_),
+        }
 
-        let params = vec![create_category.category.name.to_string()];
+        let params = vec![name, edit_author.author.id.to_string()];
+        match HelperMySql::execute_with_params("UPDATE authors SET name = ? WHERE id = ?", params)
+            .await
+        {
+            Ok(result) if result.rows_affected() == 0 => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Autor não encontrado" })),
+            )
+                .into_response(),
+            Ok(_) => {
+                ServicesPostSearchIndex::schedule_rebuild();
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Autor editado!", edit_author).into_response()
+            }
+            Err(e_) => HelpersResponse::error_with_detail("Erro ao editar autor", e_),
+        }
+    }
+
+    pub async fn delete_author(delete_author: DeleteAuthor) -> impl IntoResponse {
+        Self::delete_taxonomy(
+            "authors",
+            "author_id",
+            delete_author.author.id,
+            delete_author.author.reassign_to,
+            None,
+        )
+        .await
+    }
+
+    pub async fn create_category(create_category: CreateCategory) -> impl IntoResponse {
+        let name = create_category.category.name.trim().to_string();
+        match Self::name_in_use("categories", &name, None).await {
+            Ok(true) => return HelpersResponse::conflict("Já existe uma categoria com esse nome"),
+            Ok(false) => {}
+            Err(e_) => return HelpersResponse::error_with_detail("Erro ao criar categoria", e_),
+        }
+
+        let query = r#"
+        INSERT INTO categories (name, parent_id)
+        VALUES (?, ?)
+        "#;
+
+        let params = vec![
+            Some(name),
+            create_category.category.parent_id.map(|id| id.to_string()),
+        ];
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(_) => HelpersResponse::success("Categoria criada!", create_category).into_response(),
@@ -273,72 +1128,501 @@ impl ModelPost {
         }
     }
 
-    pub async fn create_post(slug: &str, create_post: PostRequest) -> impl IntoResponse {
+    pub async fn edit_category(edit_category: EditCategory) -> impl IntoResponse {
+        let name = edit_category.category.name.trim().to_string();
+        match Self::name_in_use("categories", &name, Some(edit_category.category.id)).await {
+            Ok(true) => return HelpersResponse::conflict("Já existe uma categoria com esse nome"),
+            Ok(false) => {}
+            Err(e_) => return HelpersResponse::error_with_detail("Erro ao editar categoria", e_),
+        }
+
+        if let Some(Some(parent_id)) = edit_category.category.parent_id {
+            match Self::is_category_descendant(edit_category.category.id, parent_id).await {
+                Ok(true) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "status": false,
+                            "message": "A categoria pai não pode ser a própria categoria ou uma descendente"
+                        })),
+                    )
+                        .into_response()
+                }
+                Ok(false) => {}
+                Err(e_) => {
+                    return HelpersResponse::error_with_detail("Erro ao editar categoria", e_)
+                }
+            }
+        }
+
+        let mut assignments = vec!["name = ?"];
+        let mut params = vec![Some(name)];
+        if let Some(parent_id) = edit_category.category.parent_id {
+            assignments.push("parent_id = ?");
+            params.push(parent_id.map(|id| id.to_string()));
+        }
+        params.push(Some(edit_category.category.id.to_string()));
+
+        let query = format!(
+            "UPDATE categories SET {} WHERE id = ?",
+            assignments.join(", ")
+        );
+        match HelperMySql::execute_with_params(&query, params).await {
+            Ok(result) if result.rows_affected() == 0 => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Categoria não encontrada" })),
+            )
+                .into_response(),
+            Ok(_) => {
+                ServicesPostSearchIndex::schedule_rebuild();
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Categoria editada!", edit_category).into_response()
+            }
+            Err(e_) => HelpersResponse::error_with_detail("Erro ao editar categoria", e_),
+        }
+    }
+
+    pub async fn delete_category(delete_category: DeleteCategory) -> impl IntoResponse {
+        // As filhas sobem um nível para não perder a hierarquia
+        let reparent_children = r#"
+            UPDATE categories child
+            INNER JOIN categories removed ON child.parent_id = removed.id
+            SET child.parent_id = removed.parent_id
+            WHERE removed.id = ?
+        "#;
+
+        Self::delete_taxonomy(
+            "categories",
+            "category_id",
+            delete_category.category.id,
+            delete_category.category.reassign_to,
+            Some(reparent_children),
+        )
+        .await
+    }
+
+    pub async fn select_category_tree() -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let categories = Self::get_all_categories().await?;
+        Ok(build_category_tree(&categories, None))
+    }
+
+    pub async fn select_category_breadcrumbs(
+        category_id: i32,
+    ) -> Result<Vec<serde_json::Value>, sqlx::Error> {
         let query = r#"
-        INSERT INTO posts (author_id, category_id, title, description, post_image_url, content, slug)
-        VALUES (?, ?, ?, ?, ?, ?, ?)
-    "#;
+            WITH RECURSIVE ancestors AS (
+                SELECT id, name, parent_id, 0 AS depth FROM categories WHERE id = ?
+                UNION ALL
+                SELECT c.id, c.name, c.parent_id, a.depth + 1
+                FROM categories c
+                INNER JOIN ancestors a ON c.id = a.parent_id
+            )
+            SELECT id, name FROM ancestors ORDER BY depth DESC
+        "#;
 
-        let params = vec![
+        let rows = HelperMySql::execute_query_with_params(query, vec![category_id]).await?;
+        let breadcrumbs = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+                    "name": row.try_get::<String, _>("name").unwrap_or_default(),
+                })
+            })
+            .collect();
+        Ok(breadcrumbs)
+    }
+
+    async fn is_category_descendant(category_id: i32, candidate: i32) -> Result<bool, sqlx::Error> {
+        let query = format!(
+            "SELECT COUNT(*) AS count FROM ({}) tree WHERE tree.id = ?",
+            CATEGORY_DESCENDANTS
+        );
+
+        let rows =
+            HelperMySql::execute_query_with_params(&query, vec![category_id, candidate]).await?;
+        let count: i64 = rows
+            .first()
+            .and_then(|row| row.try_get("count").ok())
+            .unwrap_or(0);
+        Ok(count > 0)
+    }
+
+    // `table` e `column` são sempre constantes internas, nunca entrada do cliente
+    async fn name_in_use(
+        table: &str,
+        name: &str,
+        exclude_id: Option<i32>,
+    ) -> Result<bool, sqlx::Error> {
+        let query = format!(
+            "SELECT COUNT(*) AS count FROM {} WHERE name = ? AND id <> ?",
+            table
+        );
+        let params = vec![name.to_string(), exclude_id.unwrap_or(0).to_string()];
+
+        let rows = HelperMySql::execute_query_with_params(&query, params).await?;
+        let count: i64 = rows
+            .first()
+            .and_then(|row| row.try_get("count").ok())
+            .unwrap_or(0);
+        Ok(count > 0)
+    }
+
+    async fn select_taxonomy_page(
+        table: &str,
+        column: &str,
+        id: i32,
+        not_found: &str,
+    ) -> Result<serde_json::Value, ApiError> {
+        let query = format!(
+            r#"
+            SELECT x.id, x.name,
+                (SELECT COUNT(*) FROM posts p WHERE p.{} = x.id AND {}) AS post_count
+            FROM {} x
+            WHERE x.id = ?
+            "#,
+            column, PUBLISHED_CONDITION, table
+        );
+
+        match HelperMySql::execute_query_with_params(&query, vec![id]).await {
+            Ok(rows) => match rows.first() {
+                Some(row) => Ok(json!({
+                    "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+                    "name": row.try_get::<String, _>("name").unwrap_or_default(),
+                    "post_count": row.try_get::<i64, _>("post_count").unwrap_or_default(),
+                })),
+                None => Err(ApiError {
+                    status_code: StatusCode::NOT_FOUND,
+                    message: not_found.to_string(),
+                }),
+            },
+            Err(err) => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar registro: {}", err),
+            }),
+        }
+    }
+
+    async fn delete_taxonomy(
+        table: &str,
+        column: &str,
+        id: i32,
+        reassign_to: Option<i32>,
+        before_delete: Option<&str>,
+    ) -> Response {
+        let count_query = format!("SELECT COUNT(*) AS count FROM posts WHERE {} = ?", column);
+        let referencing: i64 = match HelperMySql::execute_query_with_params(&count_query, vec![id])
+            .await
+        {
+            Ok(rows) => rows
+                .first()
+                .and_then(|row| row.try_get("count").ok())
+                .unwrap_or(0),
+            Err(e_) => return HelpersResponse::error_with_detail("Erro ao remover registro", e_),
+        };
+
+        if referencing > 0 {
+            let target = match reassign_to {
+                Some(target) if target != id => target,
+                _ => {
+                    return (
+                        StatusCode::CONFLICT,
+                        Json(json!({
+                            "status": false,
+                            "message": "Registro ainda possui posts; informe reassign_to para transferi-los",
+                            "post_count": referencing,
+                        })),
+                    )
+                        .into_response()
+                }
+            };
+
+            let exists_query = format!("SELECT COUNT(*) AS count FROM {} WHERE id = ?", table);
+            let exists =
+                match HelperMySql::execute_query_with_params(&exists_query, vec![target]).await {
+                    Ok(rows) => {
+                        rows.first()
+                            .and_then(|row| row.try_get::<i64, _>("count").ok())
+                            .unwrap_or(0)
+                            > 0
+                    }
+                    Err(e_) => {
+                        return HelpersResponse::error_with_detail("Erro ao remover registro", e_)
+                    }
+                };
+
+            if !exists {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "status": false, "message": "reassign_to não encontrado" })),
+                )
+                    .into_response();
+            }
+
+            let reassign_query = format!("UPDATE posts SET {} = ? WHERE {} = ?", column, column);
+            if let Err(e_) =
+                HelperMySql::execute_with_params(&reassign_query, vec![target, id]).await
+            {
+                return HelpersResponse::error_with_detail("Erro ao transferir posts", e_);
+            }
+        }
+
+        if let Some(query) = before_delete {
+            if let Err(e_) = HelperMySql::execute_with_params(query, vec![id]).await {
+                return HelpersResponse::error_with_detail("Erro ao remover registro", e_);
+            }
+        }
+
+        let delete_query = format!("DELETE FROM {} WHERE id = ?", table);
+        match HelperMySql::execute_with_params(&delete_query, vec![id]).await {
+            Ok(result) if result.rows_affected() == 0 => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Registro não encontrado" })),
+            )
+                .into_response(),
+            Ok(_) => {
+                if referencing > 0 {
+                    ServicesPostSearchIndex::schedule_rebuild();
+                    ServicesPostRelated::invalidate();
+                }
+
+                HelpersResponse::success(
+                    "Registro removido!",
+                    json!({ "reassigned_posts": referencing }),
+                )
+            }
+            Err(e_) => HelpersResponse::error_with_detail("Erro ao remover registro", e_),
+        }
+    }
+
+    pub async fn create_post(
+        slug: &str,
+        create_post: PostRequest,
+        user_id: i32,
+    ) -> impl IntoResponse {
+        let status = create_post.post.status.unwrap_or(PostStatus::Published);
+        let tags = create_post.post.tags.clone();
+        let content = ServicesPostMarkdown::sanitize_source(&create_post.post.content);
+        let rendered = ServicesPostMarkdown::render_document(&content);
+
+        // Sem data informada, posts publicados recebem o horário atual
+        let publication_date = match (create_post.post.publication_date, status) {
+            (Some(_), _) => "?",
+            (None, PostStatus::Published) => "NOW()",
+            (None, _) => "NULL",
+        };
+
+        let query = format!(
+            r#"
+        INSERT INTO posts (author_id, category_id, title, description, post_image_url, content, content_html, toc, word_count, reading_time_minutes, slug, status, publication_date)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, {})
+    "#,
+            publication_date
+        );
+
+        let mut params = vec![
             create_post.post.author_id.to_string(),
             create_post.post.category_id.to_string(),
             create_post.post.title,
             create_post.post.description,
             create_post.post.post_image_url.unwrap_or_default(),
-            create_post.post.content,
+            content,
+            rendered.html.clone(),
+            json!(rendered.toc).to_string(),
+            rendered.word_count.to_string(),
+            rendered.reading_time_minutes().to_string(),
             slug.to_string(),
+            status.as_str().to_string(),
         ];
 
-        match HelperMySql::execute_query_with_params(query, params).await {
-            Ok(_) => HelpersResponse::success("Post criado!", slug).into_response(),
+        if let Some(date) = create_post.post.publication_date {
+            params.push(date.format("%Y-%m-%d %H:%M:%S").to_string());
+        }
+
+        match HelperMySql::execute_with_params(&query, params).await {
+            Ok(result) => {
+                let post_id = result.last_insert_id() as i32;
+                if let Some(tags) = tags {
+                    if let Err(e) = Self::sync_post_tags(post_id, &tags).await {
+                        return HelpersResponse::error_with_detail("Erro ao salvar tags", e)
+                            .into_response();
+                    }
+                }
+
+                // Depois das tags, para a revisão guardar o estado completo do post
+                if let Err(e) = Self::record_revision(post_id, Some(user_id)).await {
+                    eprintln!("Erro ao registrar revisão do post {}: {}", post_id, e);
+                }
+
+                ServicesPostSearchIndex::sync_post(post_id);
+
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Post criado!", slug).into_response()
+            }
             Err(_e) => {
                 HelpersResponse::error_with_detail("Erro ao criar post!", _e).into_response()
             }
         }
     }
 
-    pub async fn edit_post(edit_post: EditPost) -> impl IntoResponse {
-        let query = r#"
-            UPDATE posts
-            SET author_id = ?, 
-                category_id = ?, 
-                title = ?, 
-                description = ?, 
-                post_image_url = ?, 
-                content = ?, 
-                slug = ?
-            WHERE id = ?
-        "#;
+    pub async fn edit_post(
+        edit_post: EditPost,
+        expected_version: i32,
+        user_id: i32,
+    ) -> impl IntoResponse {
+        let post_id = edit_post.post.id;
+        let tags = edit_post.post.tags.clone();
+        let content = ServicesPostMarkdown::sanitize_source(&edit_post.post.content);
+        let rendered = ServicesPostMarkdown::render_document(&content);
 
-        let params = vec![
+        // Posts anteriores ao histórico ganham uma revisão com o estado original
+        if let Err(e) = Self::record_initial_revision(post_id).await {
+            return HelpersResponse::error_with_detail("Erro ao registrar revisão", e)
+                .into_response();
+        }
+
+        let previous_slug = match Self::select_current_slug(post_id).await {
+            Ok(slug) => slug,
+            Err(e) => {
+                return HelpersResponse::error_with_detail("Erro ao editar post", e).into_response()
+            }
+        };
+
+        let mut assignments = vec![
+            "author_id = ?",
+            "category_id = ?",
+            "title = ?",
+            "description = ?",
+            "post_image_url = ?",
+            "content = ?",
+            "content_html = ?",
+            "toc = ?",
+            "word_count = ?",
+            "reading_time_minutes = ?",
+            "slug = ?",
+        ];
+
+        let mut params = vec![
             edit_post.post.author_id.to_string(),
             edit_post.post.category_id.to_string(),
             edit_post.post.title,
             edit_post.post.description,
             edit_post.post.post_image_url.unwrap_or_default(),
-            edit_post.post.content,
+            content,
+            rendered.html.clone(),
+            json!(rendered.toc).to_string(),
+            rendered.word_count.to_string(),
+            rendered.reading_time_minutes().to_string(),
             edit_post.post.slug,
-            edit_post.post.id.to_string(),
         ];
 
-        match HelperMySql::execute_query_with_params(query, params).await {
-            Ok(_) => HelpersResponse::success("Post editado!", "").into_response(),
+        if let Some(status) = edit_post.post.status {
+            assignments.push("status = ?");
+            params.push(status.as_str().to_string());
+        }
+
+        match (edit_post.post.publication_date, edit_post.post.status) {
+            (Some(date), _) => {
+                assignments.push("publication_date = ?");
+                params.push(date.format("%Y-%m-%d %H:%M:%S").to_string());
+            }
+            (None, Some(PostStatus::Published)) => {
+                assignments.push("publication_date = COALESCE(publication_date, NOW())");
+            }
+            _ => {}
+        }
+
+        let query = format!(
+            "UPDATE posts SET {}, version = version + 1 WHERE id = ? AND version = ?",
+            assignments.join(", ")
+        );
+        params.push(post_id.to_string());
+        params.push(expected_version.to_string());
+
+        match HelperMySql::execute_with_params(&query, params).await {
+            Ok(result) if result.rows_affected() == 0 => {
+                // Nenhuma linha alterada: o post não existe ou outra edição chegou antes
+                match Self::select_post_for_edit(post_id).await {
+                    Ok(current) => (
+                        StatusCode::CONFLICT,
+                        Json(json!({
+                            "status": false,
+                            "message": "O post foi alterado por outra pessoa",
+                            "data": current,
+                        })),
+                    )
+                        .into_response(),
+                    Err(err) => err.into_response(),
+                }
+            }
+            Ok(_) => {
+                if let Some(previous_slug) = previous_slug {
+                    if let Err(e) = Self::record_slug_change(post_id, &previous_slug).await {
+                        eprintln!("Erro ao registrar slug antigo do post {}: {}", post_id, e);
+                    }
+                }
+
+                if let Some(tags) = tags {
+                    if let Err(e) = Self::sync_post_tags(post_id, &tags).await {
+                        return HelpersResponse::error_with_detail("Erro ao salvar tags", e)
+                            .into_response();
+                    }
+                }
+
+                if let Err(e) = Self::record_revision(post_id, Some(user_id)).await {
+                    eprintln!("Erro ao registrar revisão do post {}: {}", post_id, e);
+                }
+
+                ServicesPostSearchIndex::sync_post(post_id);
+
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Post editado!", "").into_response()
+            }
             Err(e_) => {
                 HelpersResponse::error_with_detail("Erro ao editar post", e_).into_response()
             }
         }
     }
 
+    // Relógio do banco, o mesmo usado por PUBLISHED_CONDITION e pela publicação agendada
+    pub async fn select_database_now() -> Result<NaiveDateTime, sqlx::Error> {
+        let rows = HelperMySql::execute_select("SELECT NOW() AS now").await?;
+        rows.first()
+            .ok_or(sqlx::Error::RowNotFound)?
+            .try_get::<NaiveDateTime, _>("now")
+    }
+
+    pub async fn publish_scheduled_posts() -> Result<u64, sqlx::Error> {
+        let instance = HelperMySql::get_instance().expect("Database not initialized");
+        let query = r#"
+            UPDATE posts
+            SET status = 'published'
+            WHERE status = 'scheduled' AND publication_date <= NOW()
+        "#;
+
+        let result = sqlx::query(query).execute(&instance.pool).await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete_post(delete_post: DeletePost) -> impl IntoResponse {
         let query = r#"
             UPDATE posts
-            SET is_active = false
-            WHERE id = ?
+            SET is_active = false, deleted_at = NOW()
+            WHERE id = ? AND is_active = true
         "#;
 
         let params = vec![delete_post.post.id.to_string()];
-        match HelperMySql::execute_query_with_params(query, params).await {
-            Ok(_) => HelpersResponse::success("Post removido!", "").into_response(),
+        match HelperMySql::execute_with_params(query, params).await {
+            Ok(result) if result.rows_affected() == 0 => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Post não encontrado" })),
+            )
+                .into_response(),
+            Ok(_) => {
+                ServicesPostSearchIndex::sync_post(delete_post.post.id);
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Post removido!", "").into_response()
+            }
             Err(e_) => {
                 HelpersResponse::error_with_detail("Erro ao remover post", e_).into_response()
             }
@@ -346,21 +1630,13 @@ impl ModelPost {
     }
 
     pub async fn select_post_by_slug(slug: String) -> Result<serde_json::Value, ApiError> {
-        let query = r#"
-        SELECT 
-            p.id AS post_id, p.author_id, a.id AS author_id, a.name AS author_name,
-            p.category_id, c.id AS category_id, c.name AS category_name,
-            p.title, p.description, p.publication_date, p.post_image_url, 
-            p.content, p.created_at, p.updated_at, p.slug
-        FROM 
-            posts p
-        LEFT JOIN authors a ON p.author_id = a.id
-        LEFT JOIN categories c ON p.category_id = c.id
-        WHERE p.slug = ?
-        "#;
+        let query = format!(
+            "{} WHERE p.slug = ? AND {}",
+            POST_SELECT, PUBLISHED_CONDITION
+        );
 
         let params: Vec<String> = vec![slug];
-        match HelperMySql::execute_query_with_params(query, params).await {
+        match HelperMySql::execute_query_with_params(&query, params).await {
             Ok(rows) => {
                 if rows.is_empty() {
                     return Err(ApiError {
@@ -369,23 +1645,12 @@ impl ModelPost {
                     });
                 }
 
-                let row = &rows[0];
-                let post = json!({
-                    "id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
-                    "author_id": row.try_get::<i32, _>("author_id").unwrap_or_default(),
-                    "author_name": row.try_get::<String, _>("author_name").unwrap_or_default(),
-                    "category_id": row.try_get::<i32, _>("category_id").unwrap_or_default(),
-                    "category_name": row.try_get::<String, _>("category_name").unwrap_or_default(),
-                    "title": row.try_get::<String, _>("title").unwrap_or_default(),
-                    "description": row.try_get::<String, _>("description").unwrap_or_default(),
-                    "post_image_url": row.try_get::<Option<String>, _>("post_image_url").unwrap_or(None),
-                    "content": row.try_get::<String, _>("content").unwrap_or_default(),
-                    "slug": row.try_get::<String, _>("slug").unwrap_or_default(),
-                    "is_active": row.try_get::<i32, _>("is_active").unwrap_or_default(),
-                    "publication_date": row.try_get::<NaiveDateTime, _>("publication_date").unwrap_or_default(),
-                    "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_default(),
-                    "updated_at": row.try_get::<DateTime<Utc>, _>("updated_at").unwrap_or_default(),
-                });
+                let mut post = post_row_to_json(&rows[0]);
+                let category_id = rows[0].try_get::<i32, _>("category_id").unwrap_or_default();
+                post["breadcrumbs"] = json!(Self::select_category_breadcrumbs(category_id)
+                    .await
+                    .unwrap_or_default());
+                attach_reading_info(&mut post, &rows[0]);
 
                 Ok(post)
             }
@@ -395,4 +1660,956 @@ impl ModelPost {
             }),
         }
     }
+
+    pub async fn select_current_slug(post_id: i32) -> Result<Option<String>, sqlx::Error> {
+        let rows = HelperMySql::execute_query_with_params(
+            "SELECT slug FROM posts WHERE id = ?",
+            vec![post_id],
+        )
+        .await?;
+        Ok(rows.first().and_then(|row| row.try_get("slug").ok()))
+    }
+
+    // Guarda o slug anterior quando ele muda; se o post voltar a um slug antigo,
+    // a entrada correspondente deixa de ser um redirecionamento
+    pub async fn record_slug_change(post_id: i32, previous_slug: &str) -> Result<(), sqlx::Error> {
+        let current_slug = match Self::select_current_slug(post_id).await? {
+            Some(slug) if slug != previous_slug => slug,
+            _ => return Ok(()),
+        };
+
+        HelperMySql::execute_with_params(
+            r#"
+            INSERT INTO post_slug_history (post_id, slug) VALUES (?, ?)
+            ON DUPLICATE KEY UPDATE post_id = VALUES(post_id), created_at = NOW()
+            "#,
+            vec![post_id.to_string(), previous_slug.to_string()],
+        )
+        .await?;
+
+        HelperMySql::execute_with_params(
+            "DELETE FROM post_slug_history WHERE slug = ?",
+            vec![current_slug],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    // Slug atual de um post publicado que já respondeu pelo slug informado
+    pub async fn select_redirect_slug(slug: &str) -> Result<Option<String>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT p.slug
+            FROM post_slug_history h
+            INNER JOIN posts p ON p.id = h.post_id
+            WHERE h.slug = ? AND {}
+            "#,
+            PUBLISHED_CONDITION
+        );
+
+        let rows = HelperMySql::execute_query_with_params(&query, vec![slug.to_string()]).await?;
+        Ok(rows.first().and_then(|row| row.try_get("slug").ok()))
+    }
+
+    pub async fn add_post_views(views: &[(i32, u32)]) -> Result<(), sqlx::Error> {
+        let placeholders = vec!["(?, CURDATE(), ?)"; views.len()].join(", ");
+        let query = format!(
+            "INSERT INTO post_views (post_id, viewed_on, views) VALUES {} \
+             ON DUPLICATE KEY UPDATE views = views + VALUES(views)",
+            placeholders
+        );
+
+        let params: Vec<i64> = views
+            .iter()
+            .flat_map(|(post_id, count)| [*post_id as i64, *count as i64])
+            .collect();
+        HelperMySql::execute_with_params(&query, params).await?;
+        Ok(())
+    }
+
+    pub async fn select_popular_posts(
+        days: u32,
+        limit: u32,
+    ) -> Result<Vec<serde_json::Value>, ApiError> {
+        let ranking_query = format!(
+            r#"
+            SELECT v.post_id, CAST(SUM(v.views) AS SIGNED) AS views
+            FROM post_views v
+            INNER JOIN posts p ON p.id = v.post_id
+            WHERE v.viewed_on > CURDATE() - INTERVAL ? DAY AND {}
+            GROUP BY v.post_id
+            ORDER BY views DESC
+            LIMIT {}
+            "#,
+            PUBLISHED_CONDITION, limit
+        );
+
+        let error = |err: sqlx::Error| ApiError {
+            status_code: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Erro ao buscar posts populares: {}", err),
+        };
+
+        let ranking: Vec<(i32, i64)> =
+            HelperMySql::execute_query_with_params(&ranking_query, vec![days])
+                .await
+                .map_err(error)?
+                .iter()
+                .map(|row| {
+                    (
+                        row.try_get::<i32, _>("post_id").unwrap_or_default(),
+                        row.try_get::<i64, _>("views").unwrap_or_default(),
+                    )
+                })
+                .collect();
+
+        if ranking.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ids: Vec<i32> = ranking.iter().map(|(post_id, _)| *post_id).collect();
+        let query = format!(
+            "{} WHERE p.id IN ({})",
+            POST_SELECT,
+            vec!["?"; ids.len()].join(", ")
+        );
+        let rows = HelperMySql::execute_query_with_params(&query, ids)
+            .await
+            .map_err(error)?;
+
+        let posts = ranking
+            .into_iter()
+            .filter_map(|(post_id, views)| {
+                let row = rows
+                    .iter()
+                    .find(|row| row.try_get::<i32, _>("post_id").ok() == Some(post_id))?;
+                let mut post = post_row_to_json(row);
+                post["views"] = json!(views);
+                Some(post)
+            })
+            .collect();
+
+        Ok(posts)
+    }
+
+    pub async fn select_post_view_stats(post_id: i32) -> Result<serde_json::Value, sqlx::Error> {
+        let query = r#"
+            SELECT
+                CAST(COALESCE(SUM(views), 0) AS SIGNED) AS total,
+                CAST(COALESCE(SUM(CASE WHEN viewed_on > CURDATE() - INTERVAL 7 DAY THEN views END), 0) AS SIGNED) AS last_7_days,
+                CAST(COALESCE(SUM(CASE WHEN viewed_on > CURDATE() - INTERVAL 30 DAY THEN views END), 0) AS SIGNED) AS last_30_days
+            FROM post_views
+            WHERE post_id = ?
+        "#;
+        let rows = HelperMySql::execute_query_with_params(query, vec![post_id]).await?;
+        let totals = rows.first();
+        let total = |column: &str| {
+            totals
+                .and_then(|row| row.try_get::<i64, _>(column).ok())
+                .unwrap_or_default()
+        };
+
+        let daily_query = r#"
+            SELECT viewed_on, views
+            FROM post_views
+            WHERE post_id = ? AND viewed_on > CURDATE() - INTERVAL 30 DAY
+            ORDER BY viewed_on
+        "#;
+        let daily: Vec<serde_json::Value> =
+            HelperMySql::execute_query_with_params(daily_query, vec![post_id])
+                .await?
+                .iter()
+                .map(|row| {
+                    json!({
+                        "date": row.try_get::<NaiveDate, _>("viewed_on").ok(),
+                        "views": row.try_get::<i32, _>("views").unwrap_or_default(),
+                    })
+                })
+                .collect();
+
+        Ok(json!({
+            "post_id": post_id,
+            "total": total("total"),
+            "last_7_days": total("last_7_days"),
+            "last_30_days": total("last_30_days"),
+            "daily": daily,
+        }))
+    }
+
+    // Marca a reação se ainda não existir e desmarca caso contrário
+    pub async fn toggle_reaction(
+        post_id: i32,
+        user_id: i32,
+        reaction: ReactionType,
+    ) -> Result<bool, ApiError> {
+        let error = |err: sqlx::Error| ApiError {
+            status_code: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Erro ao registrar reação: {}", err),
+        };
+
+        let removed = HelperMySql::execute_with_params(
+            "DELETE FROM post_reactions WHERE post_id = ? AND user_id = ? AND reaction = ?",
+            vec![
+                post_id.to_string(),
+                user_id.to_string(),
+                reaction.as_str().to_string(),
+            ],
+        )
+        .await
+        .map_err(error)?;
+
+        if removed.rows_affected() > 0 {
+            return Ok(false);
+        }
+
+        // Duas requisições simultâneas podem chegar aqui; o IGNORE faz a segunda
+        // encontrar a reação já gravada em vez de estourar a chave única
+        let query = format!(
+            r#"
+            INSERT IGNORE INTO post_reactions (post_id, user_id, reaction)
+            SELECT p.id, ?, ? FROM posts p WHERE p.id = ? AND {}
+            "#,
+            PUBLISHED_CONDITION
+        );
+        let inserted = HelperMySql::execute_with_params(
+            &query,
+            vec![
+                user_id.to_string(),
+                reaction.as_str().to_string(),
+                post_id.to_string(),
+            ],
+        )
+        .await
+        .map_err(error)?;
+
+        if inserted.rows_affected() > 0 {
+            return Ok(true);
+        }
+
+        let existing = HelperMySql::execute_query_with_params(
+            "SELECT 1 FROM post_reactions WHERE post_id = ? AND user_id = ? AND reaction = ?",
+            vec![
+                post_id.to_string(),
+                user_id.to_string(),
+                reaction.as_str().to_string(),
+            ],
+        )
+        .await
+        .map_err(error)?;
+
+        if existing.is_empty() {
+            return Err(ApiError {
+                status_code: StatusCode::NOT_FOUND,
+                message: "Post não encontrado".to_string(),
+            });
+        }
+
+        Ok(true)
+    }
+
+    pub async fn select_reaction_counts(post_id: i32) -> Result<serde_json::Value, sqlx::Error> {
+        let rows = HelperMySql::execute_query_with_params(
+            "SELECT reaction, COUNT(*) AS total FROM post_reactions WHERE post_id = ? GROUP BY reaction",
+            vec![post_id],
+        )
+        .await?;
+
+        let counts: serde_json::Map<String, serde_json::Value> = ReactionType::ALL
+            .iter()
+            .map(|reaction| {
+                let total = rows
+                    .iter()
+                    .find(|row| {
+                        row.try_get::<String, _>("reaction").ok().as_deref()
+                            == Some(reaction.as_str())
+                    })
+                    .and_then(|row| row.try_get::<i64, _>("total").ok())
+                    .unwrap_or_default();
+                (reaction.as_str().to_string(), json!(total))
+            })
+            .collect();
+
+        Ok(serde_json::Value::Object(counts))
+    }
+
+    pub async fn select_user_reactions(
+        post_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let rows = HelperMySql::execute_query_with_params(
+            "SELECT reaction FROM post_reactions WHERE post_id = ? AND user_id = ? ORDER BY reaction",
+            vec![post_id, user_id],
+        )
+        .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| row.try_get("reaction").ok())
+            .collect())
+    }
+
+    // Posts publicados no formato do payload público, para alimentar o índice de busca
+    pub async fn select_indexable_posts(
+        post_id: Option<i32>,
+    ) -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let rows = match post_id {
+            Some(post_id) => {
+                let query = format!("{} WHERE p.id = ? AND {}", POST_SELECT, PUBLISHED_CONDITION);
+                HelperMySql::execute_query_with_params(&query, vec![post_id]).await?
+            }
+            None => {
+                let query = format!("{} WHERE {}", POST_SELECT, PUBLISHED_CONDITION);
+                HelperMySql::execute_select(query).await?
+            }
+        };
+
+        Ok(rows.iter().map(post_row_to_json).collect())
+    }
+
+    pub async fn select_post_for_edit(post_id: i32) -> Result<serde_json::Value, ApiError> {
+        let query = format!("{} WHERE p.id = ? AND p.is_active = 1", POST_SELECT);
+
+        match HelperMySql::execute_query_with_params(&query, vec![post_id]).await {
+            Ok(rows) => match rows.first() {
+                Some(row) => Ok(post_row_to_json(row)),
+                None => Err(ApiError {
+                    status_code: StatusCode::NOT_FOUND,
+                    message: "Post não encontrado".to_string(),
+                }),
+            },
+            Err(err) => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar o post: {}", err),
+            }),
+        }
+    }
+
+    pub async fn select_trashed_posts() -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let query = format!(
+            "{} WHERE p.is_active = 0 ORDER BY p.deleted_at DESC",
+            POST_SELECT
+        );
+
+        let rows = HelperMySql::execute_select(query).await?;
+        let posts = rows
+            .iter()
+            .map(|row| {
+                let mut post = post_row_to_json(row);
+                post["deleted_at"] = json!(row
+                    .try_get::<Option<DateTime<Utc>>, _>("deleted_at")
+                    .unwrap_or(None));
+                post
+            })
+            .collect();
+        Ok(posts)
+    }
+
+    pub async fn restore_post(restore_post: DeletePost) -> impl IntoResponse {
+        let query = r#"
+            UPDATE posts
+            SET is_active = true, deleted_at = NULL
+            WHERE id = ? AND is_active = false
+        "#;
+
+        let params = vec![restore_post.post.id.to_string()];
+        match HelperMySql::execute_with_params(query, params).await {
+            Ok(result) if result.rows_affected() == 0 => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Post não encontrado na lixeira" })),
+            )
+                .into_response(),
+            Ok(_) => {
+                ServicesPostSearchIndex::sync_post(restore_post.post.id);
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Post restaurado!", "").into_response()
+            }
+            Err(e_) => {
+                HelpersResponse::error_with_detail("Erro ao restaurar post", e_).into_response()
+            }
+        }
+    }
+
+    pub async fn purge_trashed_posts(retention_days: u32) -> impl IntoResponse {
+        let instance = match HelperMySql::get_instance() {
+            Some(instance) => instance,
+            None => return HelpersResponse::error("Database not initialized").into_response(),
+        };
+
+        let result: Result<u64, sqlx::Error> = async {
+            let mut tx = instance.pool.begin().await?;
+
+            // Comentários referenciam o post, então saem antes dele
+            sqlx::query(
+                r#"
+                DELETE c FROM comments c
+                INNER JOIN posts p ON c.post_id = p.id
+                WHERE p.is_active = false
+                  AND p.deleted_at < NOW() - INTERVAL ? DAY
+                "#,
+            )
+            .bind(retention_days)
+            .execute(&mut *tx)
+            .await?;
+
+            let purged = sqlx::query(
+                r#"
+                DELETE FROM posts
+                WHERE is_active = false
+                  AND deleted_at < NOW() - INTERVAL ? DAY
+                "#,
+            )
+            .bind(retention_days)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+            tx.commit().await?;
+            Ok(purged)
+        }
+        .await;
+
+        match result {
+            Ok(purged) => HelpersResponse::success(
+                "Lixeira esvaziada!",
+                json!({ "purged": purged, "retention_days": retention_days }),
+            )
+            .into_response(),
+            Err(e_) => {
+                HelpersResponse::error_with_detail("Erro ao esvaziar lixeira", e_).into_response()
+            }
+        }
+    }
+
+    pub async fn record_revision(post_id: i32, user_id: Option<i32>) -> Result<(), sqlx::Error> {
+        let query = format!(
+            r#"
+            INSERT INTO post_revisions
+                (post_id, user_id, author_id, category_id, title, description,
+                 post_image_url, content, slug, status, publication_date, tag_names)
+            SELECT id, ?, author_id, category_id, title, description,
+                   post_image_url, content, slug, status, publication_date, {}
+            FROM posts p
+            WHERE p.id = ?
+            "#,
+            REVISION_TAG_NAMES
+        );
+
+        let params = vec![user_id, Some(post_id)];
+        HelperMySql::execute_with_params(&query, params).await?;
+        Ok(())
+    }
+
+    async fn record_initial_revision(post_id: i32) -> Result<(), sqlx::Error> {
+        let query = format!(
+            r#"
+            INSERT INTO post_revisions
+                (post_id, user_id, author_id, category_id, title, description,
+                 post_image_url, content, slug, status, publication_date, tag_names)
+            SELECT id, NULL, author_id, category_id, title, description,
+                   post_image_url, content, slug, status, publication_date, {}
+            FROM posts p
+            WHERE p.id = ?
+              AND NOT EXISTS (SELECT 1 FROM post_revisions r WHERE r.post_id = p.id)
+            "#,
+            REVISION_TAG_NAMES
+        );
+
+        HelperMySql::execute_with_params(&query, vec![post_id]).await?;
+        Ok(())
+    }
+
+    pub async fn select_revisions(post_id: i32) -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let query = r#"
+            SELECT r.id, r.post_id, r.user_id, u.name AS user_name, r.title, r.slug,
+                   r.status, r.created_at
+            FROM post_revisions r
+            LEFT JOIN users u ON r.user_id = u.id
+            WHERE r.post_id = ?
+            ORDER BY r.id DESC
+        "#;
+
+        let rows = HelperMySql::execute_query_with_params(query, vec![post_id]).await?;
+        let revisions = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+                    "post_id": row.try_get::<i32, _>("post_id").unwrap_or_default(),
+                    "user_id": row.try_get::<Option<i32>, _>("user_id").unwrap_or(None),
+                    "user_name": row.try_get::<Option<String>, _>("user_name").unwrap_or(None),
+                    "title": row.try_get::<String, _>("title").unwrap_or_default(),
+                    "slug": row.try_get::<String, _>("slug").unwrap_or_default(),
+                    "status": row.try_get::<String, _>("status").unwrap_or_default(),
+                    "created_at": row.try_get::<DateTime<Utc>, _>("created_at").unwrap_or_default(),
+                })
+            })
+            .collect();
+        Ok(revisions)
+    }
+
+    pub async fn select_revision(post_id: i32, revision_id: i32) -> Result<PostRevision, ApiError> {
+        let query = "SELECT * FROM post_revisions WHERE post_id = ? AND id = ?";
+
+        match HelperMySql::execute_query_with_params(query, vec![post_id, revision_id]).await {
+            Ok(rows) => match rows.first() {
+                Some(row) => PostRevision::from_row(row).map_err(|err| ApiError {
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: format!("Erro ao ler revisão: {}", err),
+                }),
+                None => Err(ApiError {
+                    status_code: StatusCode::NOT_FOUND,
+                    message: format!("Revisão {} não encontrada", revision_id),
+                }),
+            },
+            Err(err) => Err(ApiError {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Erro ao buscar revisão: {}", err),
+            }),
+        }
+    }
+
+    pub fn diff_revisions(from: &PostRevision, to: &PostRevision) -> serde_json::Value {
+        let from_header = format!("revision {}", from.id);
+        let to_header = format!("revision {}", to.id);
+        let from_image = from.post_image_url.clone().unwrap_or_default();
+        let to_image = to.post_image_url.clone().unwrap_or_default();
+        let from_date = from
+            .publication_date
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+        let to_date = to
+            .publication_date
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+        let from_tags = from.tag_names.clone().unwrap_or_default();
+        let to_tags = to.tag_names.clone().unwrap_or_default();
+
+        let fields = [
+            ("title", from.title.as_str(), to.title.as_str()),
+            (
+                "description",
+                from.description.as_str(),
+                to.description.as_str(),
+            ),
+            ("content", from.content.as_str(), to.content.as_str()),
+            ("slug", from.slug.as_str(), to.slug.as_str()),
+            ("status", from.status.as_str(), to.status.as_str()),
+            ("post_image_url", from_image.as_str(), to_image.as_str()),
+            ("publication_date", from_date.as_str(), to_date.as_str()),
+            ("tags", from_tags.as_str(), to_tags.as_str()),
+        ];
+
+        let mut diff = serde_json::Map::new();
+        for (field, old, new) in fields {
+            if old == new {
+                continue;
+            }
+
+            let unified = TextDiff::from_lines(old, new)
+                .unified_diff()
+                .context_radius(3)
+                .header(&from_header, &to_header)
+                .to_string();
+            diff.insert(field.to_string(), json!(unified));
+        }
+
+        json!({
+            "from": from.id,
+            "to": to.id,
+            "changes": diff,
+        })
+    }
+
+    pub async fn rollback_post(post_id: i32, revision_id: i32, user_id: i32) -> impl IntoResponse {
+        let revision = match Self::select_revision(post_id, revision_id).await {
+            Ok(revision) => revision,
+            Err(err) => return err.into_response(),
+        };
+
+        // Revisões gravadas antes da migração 0018 não guardam data nem tags (tag_names NULL),
+        // então esses campos ficam como estão
+        let query = r#"
+            UPDATE posts p
+            INNER JOIN post_revisions r ON r.post_id = p.id
+            SET p.author_id = r.author_id,
+                p.category_id = r.category_id,
+                p.title = r.title,
+                p.description = r.description,
+                p.post_image_url = r.post_image_url,
+                p.content = r.content,
+                p.slug = r.slug,
+                p.status = r.status,
+                p.publication_date = IF(r.tag_names IS NULL, p.publication_date, r.publication_date),
+                p.version = p.version + 1
+            WHERE p.id = ? AND r.id = ?
+        "#;
+
+        let previous_slug = match Self::select_current_slug(post_id).await {
+            Ok(slug) => slug,
+            Err(e) => {
+                return HelpersResponse::error_with_detail("Erro ao restaurar revisão", e)
+                    .into_response()
+            }
+        };
+
+        match HelperMySql::execute_with_params(query, vec![post_id, revision_id]).await {
+            Ok(_) => {
+                if let Some(tag_names) = &revision.tag_names {
+                    let tags: Vec<String> = tag_names
+                        .split(',')
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    if let Err(e) = Self::sync_post_tags(post_id, &tags).await {
+                        return HelpersResponse::error_with_detail("Erro ao restaurar tags", e)
+                            .into_response();
+                    }
+                }
+
+                if let Some(previous_slug) = previous_slug {
+                    if let Err(e) = Self::record_slug_change(post_id, &previous_slug).await {
+                        eprintln!("Erro ao registrar slug antigo do post {}: {}", post_id, e);
+                    }
+                }
+
+                if let Err(e) = Self::refresh_content_html(post_id).await {
+                    eprintln!("Erro ao renderizar conteúdo do post {}: {}", post_id, e);
+                }
+
+                if let Err(e) = Self::record_revision(post_id, Some(user_id)).await {
+                    eprintln!("Erro ao registrar revisão do post {}: {}", post_id, e);
+                }
+
+                ServicesPostSearchIndex::sync_post(post_id);
+
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Post restaurado para a revisão!", revision_id)
+                    .into_response()
+            }
+            Err(e_) => {
+                HelpersResponse::error_with_detail("Erro ao restaurar revisão", e_).into_response()
+            }
+        }
+    }
+
+    pub async fn sync_post_tags(post_id: i32, tags: &[String]) -> Result<(), sqlx::Error> {
+        HelperMySql::execute_with_params("DELETE FROM post_tags WHERE post_id = ?", vec![post_id])
+            .await?;
+
+        for name in normalize_tag_names(tags) {
+            HelperMySql::execute_with_params(
+                "INSERT IGNORE INTO tags (name) VALUES (?)",
+                vec![name.clone()],
+            )
+            .await?;
+
+            HelperMySql::execute_with_params(
+                "INSERT IGNORE INTO post_tags (post_id, tag_id) SELECT ?, id FROM tags WHERE name = ?",
+                vec![post_id.to_string(), name],
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn select_tags_with_counts() -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT t.id, t.name, COUNT(p.id) AS post_count
+            FROM tags t
+            LEFT JOIN post_tags pt ON pt.tag_id = t.id
+            LEFT JOIN posts p ON p.id = pt.post_id AND {}
+            GROUP BY t.id, t.name
+            ORDER BY post_count DESC, t.name ASC
+            "#,
+            PUBLISHED_CONDITION
+        );
+
+        let rows = HelperMySql::execute_select(query).await?;
+        let tags = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "id": row.try_get::<i32, _>("id").unwrap_or_default(),
+                    "name": row.try_get::<String, _>("name").unwrap_or_default(),
+                    "post_count": row.try_get::<i64, _>("post_count").unwrap_or_default(),
+                })
+            })
+            .collect();
+        Ok(tags)
+    }
+
+    pub async fn create_tag(create_tag: CreateTag) -> impl IntoResponse {
+        let name = match normalize_tag_names(std::slice::from_ref(&create_tag.tag.name)).pop() {
+            Some(name) => name,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "status": false, "message": "Nome da tag inválido" })),
+                )
+                    .into_response()
+            }
+        };
+
+        match HelperMySql::execute_with_params("INSERT INTO tags (name) VALUES (?)", vec![name])
+            .await
+        {
+            Ok(_) => HelpersResponse::success("Tag criada!", create_tag).into_response(),
+            Err(e_) if is_duplicate_entry(&e_) => {
+                HelpersResponse::conflict("Já existe uma tag com esse nome").into_response()
+            }
+            Err(e_) => HelpersResponse::error_with_detail("Erro ao criar tag", e_).into_response(),
+        }
+    }
+
+    pub async fn edit_tag(edit_tag: EditTag) -> impl IntoResponse {
+        let name = match normalize_tag_names(std::slice::from_ref(&edit_tag.tag.name)).pop() {
+            Some(name) => name,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "status": false, "message": "Nome da tag inválido" })),
+                )
+                    .into_response()
+            }
+        };
+
+        let params = vec![name, edit_tag.tag.id.to_string()];
+        match HelperMySql::execute_with_params("UPDATE tags SET name = ? WHERE id = ?", params)
+            .await
+        {
+            Ok(result) if result.rows_affected() == 0 => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "status": false, "message": "Tag não encontrada" })),
+            )
+                .into_response(),
+            Ok(_) => {
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Tag editada!", edit_tag).into_response()
+            }
+            Err(e_) if is_duplicate_entry(&e_) => {
+                HelpersResponse::conflict("Já existe uma tag com esse nome").into_response()
+            }
+            Err(e_) => HelpersResponse::error_with_detail("Erro ao editar tag", e_).into_response(),
+        }
+    }
+
+    pub async fn delete_tag(delete_tag: DeleteTag) -> impl IntoResponse {
+        let params = vec![delete_tag.tag.id];
+
+        // post_tags tem ON DELETE CASCADE, então os vínculos saem junto
+        match HelperMySql::execute_with_params("DELETE FROM tags WHERE id = ?", params).await {
+            Ok(_) => {
+                ServicesPostRelated::invalidate();
+                HelpersResponse::success("Tag removida!", "").into_response()
+            }
+            Err(e_) => {
+                HelpersResponse::error_with_detail("Erro ao remover tag", e_).into_response()
+            }
+        }
+    }
+
+    // Grava HTML, sumário e tempo de leitura dos posts criados antes desses campos
+    pub async fn backfill_rendered_content() -> Result<usize, sqlx::Error> {
+        let rows = HelperMySql::execute_select(
+            "SELECT id FROM posts WHERE content_html IS NULL OR toc IS NULL \
+                OR word_count IS NULL OR reading_time_minutes IS NULL",
+        )
+        .await?;
+
+        for row in &rows {
+            Self::refresh_content_html(row.try_get::<i32, _>("id").unwrap_or_default()).await?;
+        }
+
+        Ok(rows.len())
+    }
+
+    pub async fn refresh_content_html(post_id: i32) -> Result<(), sqlx::Error> {
+        let rows = HelperMySql::execute_query_with_params(
+            "SELECT content FROM posts WHERE id = ?",
+            vec![post_id],
+        )
+        .await?;
+
+        // Revisões anteriores à sanitização podem trazer HTML bruto de volta num rollback
+        if let Some(row) = rows.first() {
+            let content: String = row.try_get("content").unwrap_or_default();
+            let content = ServicesPostMarkdown::sanitize_source(&content);
+            let rendered = ServicesPostMarkdown::render_document(&content);
+            let params = vec![
+                content,
+                rendered.html.clone(),
+                json!(rendered.toc).to_string(),
+                rendered.word_count.to_string(),
+                rendered.reading_time_minutes().to_string(),
+                post_id.to_string(),
+            ];
+            HelperMySql::execute_with_params(
+                "UPDATE posts SET content = ?, content_html = ?, toc = ?, word_count = ?, reading_time_minutes = ? WHERE id = ?",
+                params,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(cursor: &str) -> PostListQuery {
+        PostListQuery {
+            cursor: Some(cursor.to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn latest_revision_tags(post_id: i32) -> Option<String> {
+        let rows = HelperMySql::execute_query_with_params(
+            "SELECT tag_names FROM post_revisions WHERE post_id = ? ORDER BY id DESC LIMIT 1",
+            vec![post_id],
+        )
+        .await
+        .unwrap();
+        rows.first()
+            .and_then(|row| row.try_get::<Option<String>, _>("tag_names").ok())
+            .flatten()
+    }
+
+    // Precisa de um MySQL com as migrações aplicadas e ao menos um usuário:
+    // cargo test -- --ignored
+    #[tokio::test]
+    #[ignore]
+    async fn revisions_store_the_tags_sent_in_each_save() {
+        HelperMySql::init().await.unwrap();
+
+        let users = HelperMySql::execute_select("SELECT MIN(id) AS id FROM users")
+            .await
+            .unwrap();
+        let user_id = users[0].try_get::<i32, _>("id").unwrap();
+
+        let author = HelperMySql::execute_with_params(
+            "INSERT INTO authors (name) VALUES (?)",
+            vec![format!("Autor revisão {}", Utc::now().timestamp_micros())],
+        )
+        .await
+        .unwrap()
+        .last_insert_id() as i32;
+        let category = HelperMySql::execute_with_params(
+            "INSERT INTO categories (name) VALUES (?)",
+            vec![format!(
+                "Categoria revisão {}",
+                Utc::now().timestamp_micros()
+            )],
+        )
+        .await
+        .unwrap()
+        .last_insert_id() as i32;
+
+        let slug = format!("revisao-tags-{}", Utc::now().timestamp_micros());
+        let post = PostRequestItem {
+            author_id: author,
+            category_id: category,
+            title: "Revisão com tags".to_string(),
+            description: String::new(),
+            post_image_url: None,
+            content: "conteúdo".to_string(),
+            slug: slug.clone(),
+            status: Some(PostStatus::Draft),
+            publication_date: None,
+            tags: Some(vec!["rust".to_string(), "axum".to_string()]),
+        };
+        let _ = ModelPost::create_post(&slug, PostRequest { post }, user_id).await;
+
+        let rows = HelperMySql::execute_query_with_params(
+            "SELECT id FROM posts WHERE slug = ?",
+            vec![slug.clone()],
+        )
+        .await
+        .unwrap();
+        let post_id = rows[0].try_get::<i32, _>("id").unwrap();
+        assert_eq!(
+            latest_revision_tags(post_id).await.as_deref(),
+            Some("axum,rust")
+        );
+
+        let edit = EditPostItem {
+            id: post_id,
+            author_id: author,
+            category_id: category,
+            title: "Revisão com tags".to_string(),
+            description: String::new(),
+            post_image_url: None,
+            content: "conteúdo editado".to_string(),
+            slug,
+            status: None,
+            publication_date: None,
+            version: None,
+            tags: Some(vec!["tokio".to_string()]),
+        };
+        let _ = ModelPost::edit_post(EditPost { post: edit }, 1, user_id).await;
+        assert_eq!(
+            latest_revision_tags(post_id).await.as_deref(),
+            Some("tokio")
+        );
+
+        HelperMySql::execute_with_params("DELETE FROM posts WHERE id = ?", vec![post_id])
+            .await
+            .unwrap();
+        HelperMySql::execute_with_params("DELETE FROM authors WHERE id = ?", vec![author])
+            .await
+            .unwrap();
+        HelperMySql::execute_with_params("DELETE FROM categories WHERE id = ?", vec![category])
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn edit_category_distinguishes_missing_and_null_parent() {
+        let parse = |body: &str| serde_json::from_str::<EditCategoryItem>(body).unwrap();
+
+        assert_eq!(parse(r#"{"id": 1, "name": "Rust"}"#).parent_id, None);
+        assert_eq!(
+            parse(r#"{"id": 1, "name": "Rust", "parent_id": null}"#).parent_id,
+            Some(None)
+        );
+        assert_eq!(
+            parse(r#"{"id": 1, "name": "Rust", "parent_id": 3}"#).parent_id,
+            Some(Some(3))
+        );
+    }
+
+    #[test]
+    fn cursor_splits_key_and_id_at_last_underscore() {
+        let cursor = listing("titulo_com_underscore_42").cursor().ok().flatten();
+        assert_eq!(cursor, Some(("titulo_com_underscore".to_string(), 42)));
+
+        let cursor = listing("2024-05-01T10:00:00_7").cursor().ok().flatten();
+        assert_eq!(cursor, Some(("2024-05-01T10:00:00".to_string(), 7)));
+    }
+
+    #[test]
+    fn before_uses_the_cursor_format_and_counts_as_cursor() {
+        let filters = PostListQuery {
+            before: Some("2024-05-01T10:00:00_7".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filters.before().ok().flatten(),
+            Some(("2024-05-01T10:00:00".to_string(), 7))
+        );
+        assert!(filters.uses_cursor());
+        assert!(!listing("").uses_cursor());
+        assert_eq!(PostListQuery::default().page(), 1);
+    }
+
+    #[test]
+    fn cursor_is_optional_and_rejects_invalid_ids() {
+        assert!(matches!(PostListQuery::default().cursor(), Ok(None)));
+        assert!(matches!(listing("").cursor(), Ok(None)));
+        assert!(listing("2024-05-01T10:00:00").cursor().is_err());
+        assert!(listing("2024-05-01T10:00:00_abc").cursor().is_err());
+    }
 }
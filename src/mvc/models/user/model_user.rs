@@ -110,7 +110,7 @@ impl ModelUser {
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(rows) => {
-                if let Some(row) = rows.get(0) {
+                if let Some(row) = rows.first() {
                     let user = json!({
                         "id": row.try_get::<i32, _>("id").unwrap_or_default(),
                         "email": row.try_get::<String, _>("email").unwrap_or_default(),
@@ -139,7 +139,7 @@ impl ModelUser {
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(rows) => {
-                if let Some(row) = rows.get(0) {
+                if let Some(row) = rows.first() {
                     let hashed_password: String = row.try_get("password").unwrap_or_default();
 
                     if verify(&data.user.password, &hashed_password).unwrap_or(false) {
@@ -199,7 +199,7 @@ impl ModelUser {
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(rows) => {
-                if let Some(row) = rows.get(0) {
+                if let Some(row) = rows.first() {
                     let count: i64 = row.try_get("count").unwrap_or(0);
 
                     if count > 0 {
@@ -241,7 +241,7 @@ impl ModelUser {
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(rows) => {
-                if let Some(row) = rows.get(0) {
+                if let Some(row) = rows.first() {
                     let user = UserCode {
                         id: row.try_get("id").unwrap_or(0),
                         email: row.try_get("email").unwrap_or_default(),
@@ -296,7 +296,7 @@ impl ModelUser {
 
         match HelperMySql::execute_query_with_params(query, params).await {
             Ok(rows) => {
-                if let Some(row) = rows.get(0) {
+                if let Some(row) = rows.first() {
                     let user = UserCode {
                         id: row.try_get("id").unwrap_or(0),
                         email: row.try_get("email").unwrap_or_default(),
@@ -8,7 +8,7 @@ use axum::{
     middleware::from_fn,
     middleware::Next,
     response::Response,
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 use dotenv::dotenv;
@@ -20,6 +20,11 @@ async fn auth_middleware(req: Request<Body>, next: Next) -> Response {
     auth.verify_token(req, next).await
 }
 
+async fn admin_middleware(req: Request<Body>, next: Next) -> Response {
+    let auth: HelperMiddlewareToken = HelperMiddlewareToken::new();
+    auth.require_admin(req, next).await
+}
+
 pub fn create_routes() -> Router {
     dotenv().ok();
     let base_url: String = env::var("BASE_URL").expect("BASE_URL não configurada");
@@ -31,13 +36,96 @@ pub fn create_routes() -> Router {
     let public_routes =
         Router::new().route("/{id}", get(ControllerComment::get_all_comments_by_post));
 
-    let protected_routes = Router::new().route(
-        "/",
-        post(ControllerComment::post_new_comment).layer(from_fn(auth_middleware)),
-    );
+    let protected_routes = Router::new()
+        .route(
+            "/",
+            post(ControllerComment::post_new_comment).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/moderation",
+            get(ControllerComment::get_moderation_queue)
+                .put(ControllerComment::moderate_comments)
+                .layer(from_fn(admin_middleware)),
+        )
+        .route(
+            "/{id}/reactions",
+            put(ControllerComment::toggle_reaction).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/{id}",
+            put(ControllerComment::edit_comment)
+                .delete(ControllerComment::delete_comment)
+                .layer(from_fn(auth_middleware)),
+        );
 
     Router::new()
         .merge(public_routes)
         .merge(protected_routes)
         .layer(cors)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::middleware::token::Claims;
+    use axum::http::StatusCode;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use tower::Service;
+
+    const SECRET: &str = "segredo-de-teste";
+
+    fn router() -> Router {
+        env::set_var("BASE_URL", "http://localhost:3000");
+        env::set_var("TOKEN_SIGN_SECRET", SECRET);
+        Router::new().nest("/comment", create_routes())
+    }
+
+    fn bearer(role: &str) -> String {
+        let now = chrono::Utc::now().timestamp() as usize;
+        let claims = Claims {
+            sub: "leitor@blog.dev".to_string(),
+            role: role.to_string(),
+            user_id: 1,
+            exp: now + 3600,
+            iat: now,
+        };
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap();
+
+        format!("Bearer {}", token)
+    }
+
+    async fn status(method: Method, uri: &str, role: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", bearer(role))
+            .body(Body::empty())
+            .unwrap();
+
+        router().call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn moderation_routes_reject_reader_tokens() {
+        for method in [Method::GET, Method::PUT] {
+            assert_eq!(
+                status(method, "/comment/moderation", "user").await,
+                StatusCode::FORBIDDEN
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn moderation_routes_let_admins_through() {
+        // Sem corpo JSON a requisição para no extrator, depois de passar pelo middleware
+        assert_eq!(
+            status(Method::PUT, "/comment/moderation", "admin").await,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+}
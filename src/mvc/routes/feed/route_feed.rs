@@ -0,0 +1,12 @@
+use crate::mvc::controllers::feed::controller_feed::ControllerFeed;
+use axum::{routing::get, Router};
+
+pub fn create_routes() -> Router {
+    Router::new()
+        .route("/feed.xml", get(ControllerFeed::rss))
+        .route("/atom.xml", get(ControllerFeed::atom))
+        .route("/feed/category/{id}", get(ControllerFeed::rss_by_category))
+        .route("/atom/category/{id}", get(ControllerFeed::atom_by_category))
+        .route("/feed/author/{id}", get(ControllerFeed::rss_by_author))
+        .route("/atom/author/{id}", get(ControllerFeed::atom_by_author))
+}
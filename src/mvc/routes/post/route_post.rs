@@ -8,7 +8,7 @@ use axum::{
     middleware::from_fn,
     middleware::Next,
     response::Response,
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 use dotenv::dotenv;
@@ -20,6 +20,11 @@ async fn auth_middleware(req: Request<Body>, next: Next) -> Response {
     auth.verify_token(req, next).await
 }
 
+async fn reader_middleware(req: Request<Body>, next: Next) -> Response {
+    let auth: HelperMiddlewareToken = HelperMiddlewareToken::new();
+    auth.authenticate(req, next).await
+}
+
 pub fn create_routes() -> Router {
     dotenv().ok();
     let base_url: String = env::var("BASE_URL").expect("BASE_URL não configurada");
@@ -30,21 +35,36 @@ pub fn create_routes() -> Router {
 
     let public_routes = Router::new()
         .route("/", get(ControllerPost::get_all_posts))
+        .route("/author", get(ControllerPost::get_all_authors))
+        .route("/author/{id}", get(ControllerPost::get_author_by_id))
+        .route("/category", get(ControllerPost::get_all_categories))
+        .route("/category/tree", get(ControllerPost::get_category_tree))
+        .route("/category/{id}", get(ControllerPost::get_category_by_id))
+        .route("/tags", get(ControllerPost::get_all_tags))
+        .route("/search", get(ControllerPost::search_posts))
+        .route("/search/fuzzy", get(ControllerPost::search_posts_index))
+        .route("/popular", get(ControllerPost::get_popular_posts))
         .route("/{id}", get(ControllerPost::get_post_by_id))
+        .route("/{id}/related", get(ControllerPost::get_related_posts))
+        .route("/{id}/reactions", get(ControllerPost::get_post_reactions))
         .route("/slug/{slug}", get(ControllerPost::get_post_by_slug));
 
     let protected_routes = Router::new()
         .route(
-            "/create",
-            post(ControllerPost::create_post).layer(from_fn(auth_middleware)),
+            "/search/reindex",
+            post(ControllerPost::rebuild_search_index).layer(from_fn(auth_middleware)),
         )
         .route(
-            "/author",
-            get(ControllerPost::get_all_authors).layer(from_fn(auth_middleware)),
+            "/manage",
+            get(ControllerPost::get_managed_posts).layer(from_fn(auth_middleware)),
         )
         .route(
-            "/category",
-            get(ControllerPost::get_all_categories).layer(from_fn(auth_middleware)),
+            "/manage/{id}",
+            get(ControllerPost::get_managed_post).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/create",
+            post(ControllerPost::create_post).layer(from_fn(auth_middleware)),
         )
         .route(
             "/create/author",
@@ -54,6 +74,34 @@ pub fn create_routes() -> Router {
             "/create/category",
             post(ControllerPost::create_category).layer(from_fn(auth_middleware)),
         )
+        .route(
+            "/edit/author",
+            put(ControllerPost::edit_author).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/remove/author",
+            put(ControllerPost::delete_author).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/edit/category",
+            put(ControllerPost::edit_category).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/remove/category",
+            put(ControllerPost::delete_category).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/create/tag",
+            post(ControllerPost::create_tag).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/edit/tag",
+            put(ControllerPost::edit_tag).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/remove/tag",
+            put(ControllerPost::delete_tag).layer(from_fn(auth_middleware)),
+        )
         .route(
             "/edit",
             put(ControllerPost::edit_post).layer(from_fn(auth_middleware)),
@@ -61,6 +109,38 @@ pub fn create_routes() -> Router {
         .route(
             "/remove",
             put(ControllerPost::delete_post).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/{id}/reactions",
+            put(ControllerPost::toggle_reaction).layer(from_fn(reader_middleware)),
+        )
+        .route(
+            "/{id}/views",
+            get(ControllerPost::get_post_view_stats).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/{id}/revisions",
+            get(ControllerPost::get_post_revisions).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/{id}/revisions/diff",
+            get(ControllerPost::get_revision_diff).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/{id}/revisions/{revision_id}/rollback",
+            put(ControllerPost::rollback_post).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/trash",
+            get(ControllerPost::get_trashed_posts).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/trash/restore",
+            put(ControllerPost::restore_post).layer(from_fn(auth_middleware)),
+        )
+        .route(
+            "/trash/purge",
+            delete(ControllerPost::purge_trashed_posts).layer(from_fn(auth_middleware)),
         );
 
     Router::new()
@@ -68,3 +148,120 @@ pub fn create_routes() -> Router {
         .merge(protected_routes)
         .layer(cors)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::middleware::token::Claims;
+    use axum::http::StatusCode;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use tower::Service;
+
+    const SECRET: &str = "segredo-de-teste";
+
+    fn router() -> Router {
+        env::set_var("BASE_URL", "http://localhost:3000");
+        env::set_var("TOKEN_SIGN_SECRET", SECRET);
+        Router::new().nest("/post", create_routes())
+    }
+
+    fn bearer(role: &str) -> String {
+        let now = chrono::Utc::now().timestamp() as usize;
+        let claims = Claims {
+            sub: "leitor@blog.dev".to_string(),
+            role: role.to_string(),
+            user_id: 1,
+            exp: now + 3600,
+            iat: now,
+        };
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap();
+
+        format!("Bearer {}", token)
+    }
+
+    async fn status(method: Method, uri: &str, role: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", bearer(role))
+            .body(Body::empty())
+            .unwrap();
+
+        router().call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn trash_routes_reject_reader_tokens() {
+        for (method, uri) in [
+            (Method::GET, "/post/trash"),
+            (Method::PUT, "/post/trash/restore"),
+            (Method::DELETE, "/post/trash/purge"),
+        ] {
+            assert_eq!(status(method, uri, "user").await, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[tokio::test]
+    async fn admin_routes_reject_reader_tokens() {
+        for (method, uri) in [
+            (Method::GET, "/post/manage"),
+            (Method::POST, "/post/create"),
+            (Method::PUT, "/post/edit"),
+            (Method::PUT, "/post/remove"),
+        ] {
+            assert_eq!(status(method, uri, "user").await, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[tokio::test]
+    async fn revision_routes_reject_reader_tokens() {
+        for (method, uri) in [
+            (Method::GET, "/post/1/revisions"),
+            (Method::GET, "/post/1/revisions/diff?from=1&to=2"),
+            (Method::PUT, "/post/1/revisions/1/rollback"),
+        ] {
+            assert_eq!(status(method, uri, "user").await, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[tokio::test]
+    async fn search_reindex_rejects_reader_tokens() {
+        assert_eq!(
+            status(Method::POST, "/post/search/reindex", "user").await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn view_stats_reject_reader_tokens() {
+        assert_eq!(
+            status(Method::GET, "/post/1/views", "user").await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn reactions_stay_open_to_reader_tokens() {
+        // Sem corpo JSON a requisição para no extrator, depois de passar pelo middleware
+        assert_eq!(
+            status(Method::PUT, "/post/1/reactions", "user").await,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+
+    #[tokio::test]
+    async fn protected_routes_require_a_token() {
+        let request = Request::builder()
+            .uri("/post/trash")
+            .body(Body::empty())
+            .unwrap();
+        let response = router().call(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,9 @@
+use crate::mvc::controllers::sitemap::controller_sitemap::ControllerSitemap;
+use axum::{routing::get, Router};
+
+pub fn create_routes() -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(ControllerSitemap::sitemap))
+        .route("/sitemap/{page}", get(ControllerSitemap::sitemap_page))
+        .route("/robots.txt", get(ControllerSitemap::robots))
+}
@@ -0,0 +1,229 @@
+use crate::mvc::models::comment::model_comment::ModelComment;
+use deunicode::deunicode;
+use dotenv::dotenv;
+use std::collections::HashSet;
+
+const DEFAULT_WORDS: [&str; 8] = [
+    "viagra",
+    "cialis",
+    "casino",
+    "cassino",
+    "apostas",
+    "emprestimo",
+    "forex",
+    "replica",
+];
+
+// Pesos de cada sinal na pontuação
+const EXTRA_LINK_WEIGHT: f64 = 1.5;
+const BLACKLIST_WEIGHT: f64 = 3.0;
+const DUPLICATE_WEIGHT: f64 = 4.0;
+const VELOCITY_WEIGHT: f64 = 3.0;
+const BAYES_WEIGHT: f64 = 4.0;
+
+// Treino mínimo por rótulo antes do filtro bayesiano influenciar a nota
+const BAYES_MIN_DOCUMENTS: i64 = 20;
+const BAYES_INTERESTING_TOKENS: usize = 15;
+
+pub struct SpamVerdict {
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+pub struct ServicesCommentSpam;
+
+impl ServicesCommentSpam {
+    fn env_list(name: &str) -> Option<Vec<String>> {
+        dotenv().ok();
+        std::env::var(name).ok().map(|value| {
+            value
+                .split(',')
+                .map(|item| item.trim().to_lowercase())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+    }
+
+    fn env_number<T: std::str::FromStr>(name: &str, default: T) -> T {
+        dotenv().ok();
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse::<T>().ok())
+            .unwrap_or(default)
+    }
+
+    pub fn threshold() -> f64 {
+        Self::env_number("COMMENT_SPAM_THRESHOLD", 5.0)
+    }
+
+    pub fn tokenize(content: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        deunicode(content)
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| (3..=30).contains(&token.len()))
+            .filter(|token| seen.insert(token.to_string()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn count_links(content: &str) -> usize {
+        let content = content.to_lowercase();
+        ["http://", "https://", "www."]
+            .iter()
+            .map(|marker| content.matches(marker).count())
+            .sum::<usize>()
+            .saturating_sub(content.matches("://www.").count())
+    }
+
+    // Sinais que dependem só do texto: excesso de links e termos bloqueados
+    fn content_score(content: &str) -> SpamVerdict {
+        let mut verdict = SpamVerdict {
+            score: 0.0,
+            reasons: vec![],
+        };
+        let lowered = deunicode(content).to_lowercase();
+
+        let max_links: usize = Self::env_number("COMMENT_SPAM_MAX_LINKS", 2);
+        let links = Self::count_links(content);
+        if links > max_links {
+            verdict.score += (links - max_links) as f64 * EXTRA_LINK_WEIGHT;
+            verdict.reasons.push(format!("{} links", links));
+        }
+
+        let words = Self::env_list("COMMENT_SPAM_WORDS")
+            .unwrap_or_else(|| DEFAULT_WORDS.iter().map(|w| w.to_string()).collect());
+        let domains = Self::env_list("COMMENT_SPAM_DOMAINS").unwrap_or_default();
+        for blocked in words.iter().chain(domains.iter()) {
+            if lowered.contains(blocked.as_str()) {
+                verdict.score += BLACKLIST_WEIGHT;
+                verdict
+                    .reasons
+                    .push(format!("termo bloqueado: {}", blocked));
+            }
+        }
+
+        verdict
+    }
+
+    /// Soma os sinais de spam do comentário; notas altas vão para a fila de moderação.
+    /// Em edições, `comment_id` evita que o próprio comentário conte como duplicado.
+    pub async fn score(content: &str, user_id: i32, comment_id: Option<i32>) -> SpamVerdict {
+        let mut verdict = Self::content_score(content);
+
+        let duplicate_hours: i64 = Self::env_number("COMMENT_SPAM_DUPLICATE_HOURS", 24);
+        match ModelComment::count_recent_duplicates(content, duplicate_hours, comment_id).await {
+            Ok(0) => {}
+            Ok(_) => {
+                verdict.score += DUPLICATE_WEIGHT;
+                verdict.reasons.push("conteúdo duplicado".to_string());
+            }
+            Err(e) => eprintln!("Erro ao verificar comentários duplicados: {}", e),
+        }
+
+        let velocity_minutes: i64 = Self::env_number("COMMENT_SPAM_VELOCITY_MINUTES", 10);
+        let velocity_limit: i64 = Self::env_number("COMMENT_SPAM_VELOCITY_LIMIT", 5);
+        match ModelComment::count_recent_by_user(user_id, velocity_minutes, comment_id).await {
+            Ok(recent) if recent >= velocity_limit => {
+                verdict.score += VELOCITY_WEIGHT;
+                verdict.reasons.push(format!(
+                    "{} comentários em {} min",
+                    recent, velocity_minutes
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Erro ao verificar frequência de comentários: {}", e),
+        }
+
+        if Self::env_number("COMMENT_SPAM_BAYES", false) {
+            match Self::bayes_probability(content).await {
+                Ok(Some(probability)) if probability >= 0.9 => {
+                    verdict.score += BAYES_WEIGHT;
+                    verdict
+                        .reasons
+                        .push(format!("filtro bayesiano: {:.2}", probability));
+                }
+                Ok(Some(probability)) if probability <= 0.1 => {
+                    verdict.score -= BAYES_WEIGHT / 2.0;
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Erro ao consultar filtro bayesiano: {}", e),
+            }
+        }
+
+        verdict.score = verdict.score.max(0.0);
+        verdict
+    }
+
+    // Combina as palavras mais decisivas (estilo Graham); `None` enquanto o treino é insuficiente
+    async fn bayes_probability(content: &str) -> Result<Option<f64>, sqlx::Error> {
+        let (spam_documents, ham_documents) = ModelComment::select_spam_corpus().await?;
+        if spam_documents < BAYES_MIN_DOCUMENTS || ham_documents < BAYES_MIN_DOCUMENTS {
+            return Ok(None);
+        }
+
+        let tokens = Self::tokenize(content);
+        let counts = ModelComment::select_spam_token_counts(&tokens).await?;
+
+        let mut probabilities: Vec<f64> = counts
+            .into_iter()
+            .map(|(_, spam, ham)| {
+                let spam_rate = (spam as f64 + 1.0) / (spam_documents as f64 + 2.0);
+                let ham_rate = (ham as f64 + 1.0) / (ham_documents as f64 + 2.0);
+                (spam_rate / (spam_rate + ham_rate)).clamp(0.01, 0.99)
+            })
+            .collect();
+
+        if probabilities.is_empty() {
+            return Ok(None);
+        }
+
+        probabilities.sort_by(|a, b| (b - 0.5).abs().total_cmp(&(a - 0.5).abs()));
+        let log_odds: f64 = probabilities
+            .iter()
+            .take(BAYES_INTERESTING_TOKENS)
+            .map(|p| (p / (1.0 - p)).ln())
+            .sum();
+
+        Ok(Some(1.0 / (1.0 + (-log_odds).exp())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_normalizes_and_deduplicates_words() {
+        assert_eq!(
+            ServicesCommentSpam::tokenize("Promoção PROMOÇÃO de cassino!"),
+            vec!["promocao", "cassino"]
+        );
+    }
+
+    #[test]
+    fn count_links_does_not_count_www_after_scheme_twice() {
+        assert_eq!(
+            ServicesCommentSpam::count_links("veja https://www.a.com e www.b.com"),
+            2
+        );
+    }
+
+    #[test]
+    fn content_score_ignores_regular_comments() {
+        let verdict = ServicesCommentSpam::content_score("Ótimo post, obrigado!");
+
+        assert_eq!(verdict.score, 0.0);
+        assert!(verdict.reasons.is_empty());
+    }
+
+    #[test]
+    fn content_score_adds_blocked_terms_and_extra_links() {
+        let verdict = ServicesCommentSpam::content_score(
+            "Cassino online: http://a.com http://b.com http://c.com http://d.com",
+        );
+
+        assert_eq!(verdict.score, BLACKLIST_WEIGHT + 2.0 * EXTRA_LINK_WEIGHT);
+        assert_eq!(verdict.reasons, vec!["4 links", "termo bloqueado: cassino"]);
+    }
+}
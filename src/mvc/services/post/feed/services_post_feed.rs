@@ -0,0 +1,158 @@
+use crate::helpers::xml::helpers_xml::HelpersXml;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::Value;
+pub struct ServicesPostFeed;
+
+pub struct FeedChannel {
+    pub title: String,
+    pub description: String,
+    pub site_url: String,
+    pub feed_url: String,
+}
+
+fn post_str<'a>(post: &'a Value, field: &str) -> &'a str {
+    post[field].as_str().unwrap_or_default()
+}
+
+fn post_datetime(post: &Value, field: &str) -> Option<DateTime<Utc>> {
+    let raw = post[field].as_str()?;
+    raw.parse::<DateTime<Utc>>().ok().or_else(|| {
+        raw.parse::<NaiveDateTime>()
+            .ok()
+            .map(|naive| naive.and_utc())
+    })
+}
+
+impl ServicesPostFeed {
+    pub fn post_url(site_url: &str, post: &Value) -> String {
+        format!(
+            "{}/post/{}",
+            site_url.trim_end_matches('/'),
+            post_str(post, "slug")
+        )
+    }
+
+    // Data mais recente de atualização entre os posts, usada como data do feed
+    fn last_updated(posts: &[Value]) -> DateTime<Utc> {
+        posts
+            .iter()
+            .filter_map(|post| post_datetime(post, "updated_at"))
+            .max()
+            .unwrap_or_else(Utc::now)
+    }
+
+    pub fn rss(channel: &FeedChannel, posts: &[Value]) -> String {
+        let items: String = posts
+            .iter()
+            .map(|post| {
+                let url = Self::post_url(&channel.site_url, post);
+                // <author> do RSS exige e-mail; o nome do autor vai em dc:creator
+                let pub_date = post_datetime(post, "publication_date")
+                    .map(|date| format!("<pubDate>{}</pubDate>", date.to_rfc2822()))
+                    .unwrap_or_default();
+                let categories: String = std::iter::once(post_str(post, "category_name"))
+                    .chain(
+                        post["tags"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|tag| tag.as_str()),
+                    )
+                    .filter(|name| !name.is_empty())
+                    .map(|name| format!("<category>{}</category>", HelpersXml::escape(name)))
+                    .collect();
+
+                format!(
+                    "<item><title>{}</title><link>{}</link><guid isPermaLink=\"true\">{}</guid>\
+                     <description>{}</description><dc:creator>{}</dc:creator>{}{}</item>",
+                    HelpersXml::escape(post_str(post, "title")),
+                    HelpersXml::escape(&url),
+                    HelpersXml::escape(&url),
+                    HelpersXml::escape(post_str(post, "description")),
+                    HelpersXml::escape(post_str(post, "author_name")),
+                    categories,
+                    pub_date,
+                )
+            })
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+             xmlns:dc=\"http://purl.org/dc/elements/1.1/\"><channel>\
+             <title>{}</title><link>{}</link><description>{}</description>\
+             <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\
+             <lastBuildDate>{}</lastBuildDate>{}</channel></rss>",
+            HelpersXml::escape(&channel.title),
+            HelpersXml::escape(&channel.site_url),
+            HelpersXml::escape(&channel.description),
+            HelpersXml::escape(&channel.feed_url),
+            Self::last_updated(posts).to_rfc2822(),
+            items,
+        )
+    }
+
+    pub fn atom(channel: &FeedChannel, posts: &[Value]) -> String {
+        let entries: String = posts
+            .iter()
+            .map(|post| {
+                let url = Self::post_url(&channel.site_url, post);
+                let updated = post_datetime(post, "updated_at").unwrap_or_else(Utc::now);
+                let published = post_datetime(post, "publication_date")
+                    .map(|date| format!("<published>{}</published>", date.to_rfc3339()))
+                    .unwrap_or_default();
+
+                format!(
+                    "<entry><title>{}</title><link href=\"{}\"/><id>{}</id>\
+                     <updated>{}</updated>{}<author><name>{}</name></author>\
+                     <summary>{}</summary></entry>",
+                    HelpersXml::escape(post_str(post, "title")),
+                    HelpersXml::escape(&url),
+                    HelpersXml::escape(&url),
+                    updated.to_rfc3339(),
+                    published,
+                    HelpersXml::escape(post_str(post, "author_name")),
+                    HelpersXml::escape(post_str(post, "description")),
+                )
+            })
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <feed xmlns=\"http://www.w3.org/2005/Atom\">\
+             <title>{}</title><subtitle>{}</subtitle>\
+             <link href=\"{}\"/><link href=\"{}\" rel=\"self\"/>\
+             <id>{}</id><updated>{}</updated>{}</feed>",
+            HelpersXml::escape(&channel.title),
+            HelpersXml::escape(&channel.description),
+            HelpersXml::escape(&channel.site_url),
+            HelpersXml::escape(&channel.feed_url),
+            HelpersXml::escape(&channel.feed_url),
+            Self::last_updated(posts).to_rfc3339(),
+            entries,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rss_puts_author_name_in_dc_creator() {
+        let channel = FeedChannel {
+            title: "Blog".to_string(),
+            description: String::new(),
+            site_url: "https://blog.dev".to_string(),
+            feed_url: "https://blog.dev/feed.xml".to_string(),
+        };
+        let posts = vec![json!({ "title": "Olá", "slug": "ola", "author_name": "Ana" })];
+
+        let rss = ServicesPostFeed::rss(&channel, &posts);
+
+        assert!(rss.contains("xmlns:dc=\"http://purl.org/dc/elements/1.1/\""));
+        assert!(rss.contains("<dc:creator>Ana</dc:creator>"));
+        assert!(!rss.contains("<author>"));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::helpers::sanitizer::helpers_sanitizer::{HelpersSanitizer, SanitizePolicy};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::ops::Range;
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+pub struct ServicesPostMarkdown;
+
+pub struct RenderedMarkdown {
+    pub html: String,
+    pub toc: Vec<Value>,
+    pub word_count: usize,
+}
+
+impl RenderedMarkdown {
+    const WORDS_PER_MINUTE: usize = 200;
+
+    pub fn reading_time_minutes(&self) -> usize {
+        self.word_count.div_ceil(Self::WORDS_PER_MINUTE).max(1)
+    }
+}
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+
+impl ServicesPostMarkdown {
+    fn options() -> Options {
+        Options::ENABLE_TABLES
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_HEADING_ATTRIBUTES
+    }
+
+    // Destaque via classes CSS (prefixo "hl-"), o tema fica a cargo do front-end
+    fn highlight_code(language: &str, code: &str) -> String {
+        let syntax = SYNTAX_SET
+            .find_syntax_by_token(language)
+            .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+        let mut generator = ClassedHTMLGenerator::new_with_class_style(
+            syntax,
+            &SYNTAX_SET,
+            ClassStyle::SpacedPrefixed { prefix: "hl-" },
+        );
+
+        for line in LinesWithEndings::from(code) {
+            if generator
+                .parse_html_for_line_which_includes_newline(line)
+                .is_err()
+            {
+                return format!("<pre><code>{}</code></pre>", ammonia::clean_text(code));
+            }
+        }
+
+        format!(
+            "<pre class=\"hl-code\"><code class=\"language-{}\">{}</code></pre>",
+            ammonia::clean_text(language),
+            generator.finalize()
+        )
+    }
+
+    fn anchor_id(text: &str, used: &mut HashMap<String, usize>) -> String {
+        let mut base = String::new();
+        for c in text.trim().to_lowercase().chars() {
+            if c.is_alphanumeric() {
+                base.push(c);
+            } else if (c.is_whitespace() || c == '-' || c == '_') && !base.ends_with('-') {
+                base.push('-');
+            }
+        }
+        let mut base = base.trim_matches('-').to_string();
+        if base.is_empty() {
+            base = "secao".to_string();
+        }
+
+        // Títulos repetidos recebem sufixo numérico, sempre na ordem em que aparecem
+        let count = used.entry(base.clone()).or_insert(0);
+        let id = match *count {
+            0 => base,
+            n => format!("{}-{}", base, n),
+        };
+        *count += 1;
+        id
+    }
+
+    fn nest_toc(entries: &[(u8, String, String)]) -> Vec<Value> {
+        let mut toc = vec![];
+        let mut index = 0;
+
+        while index < entries.len() {
+            let (level, id, text) = &entries[index];
+            let mut end = index + 1;
+            while end < entries.len() && entries[end].0 > *level {
+                end += 1;
+            }
+
+            toc.push(json!({
+                "id": id,
+                "text": text,
+                "level": level,
+                "children": Self::nest_toc(&entries[index + 1..end]),
+            }));
+            index = end;
+        }
+
+        toc
+    }
+
+    /// Converte Markdown (CommonMark + GFM) em HTML sanitizado pela política de posts.
+    fn tag_name(fragment: &str) -> String {
+        fragment
+            .trim_start_matches(['<', '/'])
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase()
+    }
+
+    // HTML inline chega tag a tag; o ammonia fecharia cada abertura sozinho e
+    // descartaria qualquer fechamento avulso, então isso é desfeito aqui
+    fn sanitize_inline_html(fragment: &str) -> String {
+        let name = Self::tag_name(fragment);
+        if name.is_empty() {
+            return HelpersSanitizer::clean(fragment, SanitizePolicy::Post);
+        }
+
+        let closing = format!("</{}>", name);
+        if fragment.starts_with("</") {
+            let probe = format!("<{}>{}", name, closing);
+            return if HelpersSanitizer::clean(&probe, SanitizePolicy::Post) == probe {
+                closing
+            } else {
+                String::new()
+            };
+        }
+
+        let cleaned = HelpersSanitizer::clean(fragment, SanitizePolicy::Post);
+        match cleaned.strip_suffix(&closing) {
+            Some(opening) if !fragment.ends_with(&closing) => opening.to_string(),
+            _ => cleaned,
+        }
+    }
+
+    /// Passa o HTML escrito dentro do markdown pela política de posts, mantendo o resto do texto intacto.
+    pub fn sanitize_source(markdown: &str) -> String {
+        let mut replacements: Vec<(Range<usize>, String)> = vec![];
+        let mut block_end = 0;
+
+        for (event, range) in Parser::new_ext(markdown, Self::options()).into_offset_iter() {
+            if range.start < block_end {
+                continue;
+            }
+
+            let fragment = &markdown[range.clone()];
+            let cleaned = match event {
+                Event::Start(Tag::HtmlBlock) => {
+                    block_end = range.end;
+                    HelpersSanitizer::clean(fragment, SanitizePolicy::Post)
+                }
+                Event::InlineHtml(_) | Event::Html(_) => Self::sanitize_inline_html(fragment),
+                _ => continue,
+            };
+
+            if cleaned != fragment {
+                replacements.push((range, cleaned));
+            }
+        }
+
+        let mut source = markdown.to_string();
+        for (range, cleaned) in replacements.into_iter().rev() {
+            source.replace_range(range, &cleaned);
+        }
+        source
+    }
+
+    pub fn render(markdown: &str) -> String {
+        Self::render_document(markdown).html
+    }
+
+    /// Renderiza o post e extrai sumário (com âncoras nos títulos) e contagem de palavras.
+    pub fn render_document(markdown: &str) -> RenderedMarkdown {
+        let mut events: Vec<Event> = vec![];
+        let mut code_block: Option<(String, String)> = None;
+        let mut heading: Option<(Tag, Vec<Event>, String)> = None;
+        let mut toc_entries: Vec<(u8, String, String)> = vec![];
+        let mut used_ids: HashMap<String, usize> = HashMap::new();
+        let mut word_count = 0;
+
+        for event in Parser::new_ext(markdown, Self::options()) {
+            if let Event::Text(text) | Event::Code(text) = &event {
+                word_count += text.split_whitespace().count();
+            }
+
+            match (&mut code_block, &mut heading, event) {
+                (None, _, Event::Start(Tag::CodeBlock(kind))) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(info) => info
+                            .split_whitespace()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string(),
+                        CodeBlockKind::Indented => String::new(),
+                    };
+                    code_block = Some((language, String::new()));
+                }
+                (Some((_, code)), _, Event::Text(text)) => code.push_str(&text),
+                (Some(_), _, Event::End(TagEnd::CodeBlock)) => {
+                    if let Some((language, code)) = code_block.take() {
+                        let highlighted = Self::highlight_code(&language, &code);
+                        events.push(Event::Html(CowStr::from(highlighted)));
+                    }
+                }
+                (Some(_), _, _) => {}
+                (None, None, Event::Start(tag @ Tag::Heading { .. })) => {
+                    heading = Some((tag, vec![], String::new()));
+                }
+                (None, Some(_), Event::End(TagEnd::Heading(level))) => {
+                    if let Some((
+                        Tag::Heading {
+                            id, classes, attrs, ..
+                        },
+                        inner,
+                        text,
+                    )) = heading.take()
+                    {
+                        let anchor = match id {
+                            Some(id) => id.to_string(),
+                            None => Self::anchor_id(&text, &mut used_ids),
+                        };
+                        toc_entries.push((level as u8, anchor.clone(), text.trim().to_string()));
+
+                        events.push(Event::Start(Tag::Heading {
+                            level,
+                            id: Some(CowStr::from(anchor)),
+                            classes,
+                            attrs,
+                        }));
+                        events.extend(inner);
+                        events.push(Event::End(TagEnd::Heading(level)));
+                    }
+                }
+                (None, Some((_, inner, text)), event) => {
+                    if let Event::Text(value) | Event::Code(value) = &event {
+                        text.push_str(value);
+                    }
+                    inner.push(event);
+                }
+                (None, None, event) => events.push(event),
+            }
+        }
+
+        let mut unsafe_html = String::new();
+        html::push_html(&mut unsafe_html, events.into_iter());
+
+        RenderedMarkdown {
+            html: HelpersSanitizer::clean(&unsafe_html, SanitizePolicy::Post),
+            toc: Self::nest_toc(&toc_entries),
+            word_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_source_removes_scripts_and_handlers() {
+        let source = "# Título\n\n<script>alert(1)</script>\n\nTexto <img src=\"a.png\" onerror=\"alert(1)\"> fim\n";
+        let cleaned = ServicesPostMarkdown::sanitize_source(source);
+
+        assert!(!cleaned.contains("script"));
+        assert!(!cleaned.contains("onerror"));
+        assert!(cleaned.starts_with("# Título\n\n"));
+        assert!(cleaned.contains("Texto <img src=\"a.png\"> fim"));
+    }
+
+    #[test]
+    fn sanitize_source_keeps_markdown_and_allowed_html() {
+        let source = "Use `Vec<T>` com <em>cuidado</em> & calma.\n\n<div class=\"nota\">\n<p>ok</p>\n</div>\n";
+        assert_eq!(ServicesPostMarkdown::sanitize_source(source), source);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::mvc::models::post::model_post::ModelPost;
+use deunicode::deunicode;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+// Pesos de cada critério na pontuação final
+const TEXT_WEIGHT: f64 = 3.0;
+const CATEGORY_WEIGHT: f64 = 1.0;
+const TAG_WEIGHT: f64 = 0.5;
+
+pub const MAX_RELATED: usize = 20;
+
+const STOPWORDS: [&str; 40] = [
+    "que", "para", "com", "uma", "por", "mais", "como", "mas", "foi", "ele", "das", "dos", "tem",
+    "seu", "sua", "ser", "quando", "muito", "nos", "esta", "este", "isso", "ela", "entre",
+    "depois", "sem", "mesmo", "aos", "seus", "quem", "nas", "pelo", "pela", "ate", "nao", "the",
+    "and", "for", "you", "are",
+];
+
+struct RelatedDocument {
+    id: i32,
+    category_id: i32,
+    tags: HashSet<String>,
+    vector: HashMap<String, f64>,
+    summary: Value,
+}
+
+#[derive(Default)]
+struct RelatedCache {
+    // Incrementada a cada alteração para descartar cálculos iniciados antes dela
+    generation: u64,
+    corpus: Option<Arc<Vec<RelatedDocument>>>,
+    related: HashMap<i32, Vec<Value>>,
+}
+
+static CACHE: Lazy<RwLock<RelatedCache>> = Lazy::new(|| RwLock::new(RelatedCache::default()));
+
+pub struct ServicesPostRelated;
+
+impl ServicesPostRelated {
+    /// Descarta o corpus e as recomendações calculadas; chamada sempre que posts mudam.
+    pub fn invalidate() {
+        let mut cache = CACHE.write().unwrap();
+        cache.generation += 1;
+        cache.corpus = None;
+        cache.related.clear();
+    }
+
+    /// Posts mais parecidos com `post_id`, ou `None` se ele não estiver publicado.
+    pub async fn related(post_id: i32, limit: usize) -> Result<Option<Vec<Value>>, sqlx::Error> {
+        let (generation, corpus) = {
+            let cache = CACHE.read().unwrap();
+            if let Some(related) = cache.related.get(&post_id) {
+                return Ok(Some(related.iter().take(limit).cloned().collect()));
+            }
+            (cache.generation, cache.corpus.clone())
+        };
+
+        let corpus = match corpus {
+            Some(corpus) => corpus,
+            None => Arc::new(Self::build_corpus().await?),
+        };
+
+        let related = match Self::rank(&corpus, post_id) {
+            Some(related) => related,
+            None => return Ok(None),
+        };
+
+        let mut cache = CACHE.write().unwrap();
+        if cache.generation == generation {
+            cache.corpus = Some(corpus);
+            cache.related.insert(post_id, related.clone());
+        }
+
+        Ok(Some(related.into_iter().take(limit).collect()))
+    }
+
+    fn tokenize(text: &str) -> Vec<String> {
+        deunicode(text)
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| token.len() > 2 && !STOPWORDS.contains(token))
+            .map(str::to_string)
+            .collect()
+    }
+
+    async fn build_corpus() -> Result<Vec<RelatedDocument>, sqlx::Error> {
+        let posts = ModelPost::select_indexable_posts(None).await?;
+
+        let term_counts: Vec<HashMap<String, f64>> = posts
+            .iter()
+            .map(|post| {
+                let text = format!(
+                    "{} {} {}",
+                    post["title"].as_str().unwrap_or_default(),
+                    post["description"].as_str().unwrap_or_default(),
+                    post["content"].as_str().unwrap_or_default()
+                );
+                let mut counts = HashMap::new();
+                for token in Self::tokenize(&text) {
+                    *counts.entry(token).or_insert(0.0) += 1.0;
+                }
+                counts
+            })
+            .collect();
+
+        let mut document_frequency: HashMap<&str, f64> = HashMap::new();
+        for counts in &term_counts {
+            for term in counts.keys() {
+                *document_frequency.entry(term.as_str()).or_insert(0.0) += 1.0;
+            }
+        }
+
+        let total = posts.len() as f64;
+        let idf: HashMap<String, f64> = document_frequency
+            .into_iter()
+            .map(|(term, frequency)| (term.to_string(), (total / frequency).ln() + 1.0))
+            .collect();
+
+        let documents = posts
+            .into_iter()
+            .zip(term_counts)
+            .map(|(post, counts)| {
+                let length: f64 = counts.values().sum::<f64>().max(1.0);
+                let mut vector: HashMap<String, f64> = counts
+                    .into_iter()
+                    .map(|(term, count)| {
+                        let weight = (count / length) * idf.get(&term).copied().unwrap_or(1.0);
+                        (term, weight)
+                    })
+                    .collect();
+
+                let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+                if norm > 0.0 {
+                    vector.values_mut().for_each(|w| *w /= norm);
+                }
+
+                RelatedDocument {
+                    id: post["id"].as_i64().unwrap_or_default() as i32,
+                    category_id: post["category_id"].as_i64().unwrap_or_default() as i32,
+                    tags: post["tags"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|tag| tag.as_str().map(str::to_lowercase))
+                        .collect(),
+                    vector,
+                    summary: json!({
+                        "id": post["id"],
+                        "title": post["title"],
+                        "description": post["description"],
+                        "slug": post["slug"],
+                        "post_image_url": post["post_image_url"],
+                        "author_name": post["author_name"],
+                        "category_id": post["category_id"],
+                        "category_name": post["category_name"],
+                        "tags": post["tags"],
+                        "publication_date": post["publication_date"],
+                    }),
+                }
+            })
+            .collect();
+
+        Ok(documents)
+    }
+
+    fn rank(corpus: &[RelatedDocument], post_id: i32) -> Option<Vec<Value>> {
+        let target = corpus.iter().find(|document| document.id == post_id)?;
+
+        let mut scored: Vec<(f64, &RelatedDocument)> = corpus
+            .iter()
+            .filter(|document| document.id != post_id)
+            .map(|document| {
+                let (small, large) = if target.vector.len() < document.vector.len() {
+                    (&target.vector, &document.vector)
+                } else {
+                    (&document.vector, &target.vector)
+                };
+                let similarity: f64 = small
+                    .iter()
+                    .filter_map(|(term, weight)| large.get(term).map(|other| weight * other))
+                    .sum();
+
+                let shared_tags = target.tags.intersection(&document.tags).count() as f64;
+                let same_category = if document.category_id == target.category_id {
+                    1.0
+                } else {
+                    0.0
+                };
+
+                let score = similarity * TEXT_WEIGHT
+                    + same_category * CATEGORY_WEIGHT
+                    + shared_tags * TAG_WEIGHT;
+                (score, document)
+            })
+            .filter(|(score, _)| *score > 0.0)
+            .collect();
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Some(
+            scored
+                .into_iter()
+                .take(MAX_RELATED)
+                .map(|(score, document)| {
+                    let mut summary = document.summary.clone();
+                    summary["score"] = json!((score * 1000.0).round() / 1000.0);
+                    summary
+                })
+                .collect(),
+        )
+    }
+}
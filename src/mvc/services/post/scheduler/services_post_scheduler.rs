@@ -0,0 +1,35 @@
+use crate::mvc::models::post::model_post::ModelPost;
+use crate::mvc::services::post::related::services_post_related::ServicesPostRelated;
+use crate::mvc::services::post::search_index::services_post_search_index::ServicesPostSearchIndex;
+use dotenv::dotenv;
+use std::time::Duration;
+pub struct ServicesPostScheduler;
+
+impl ServicesPostScheduler {
+    pub fn spawn() {
+        dotenv().ok();
+        let interval_secs = std::env::var("POST_SCHEDULER_INTERVAL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .unwrap_or(60)
+            .max(1);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+            loop {
+                interval.tick().await;
+
+                match ModelPost::publish_scheduled_posts().await {
+                    Ok(0) => {}
+                    Ok(published) => {
+                        println!("{} post(s) agendado(s) publicado(s)", published);
+                        ServicesPostSearchIndex::schedule_rebuild();
+                        ServicesPostRelated::invalidate();
+                    }
+                    Err(e) => eprintln!("Erro ao publicar posts agendados: {}", e),
+                }
+            }
+        });
+    }
+}
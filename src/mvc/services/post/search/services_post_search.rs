@@ -0,0 +1,172 @@
+use crate::helpers::xml::helpers_xml::HelpersXml;
+pub struct ServicesPostSearch;
+
+// Mesmo valor padrão de innodb_ft_min_token_size; termos menores não estão no índice FULLTEXT
+pub const MIN_TERM_LENGTH: usize = 3;
+
+impl ServicesPostSearch {
+    pub fn terms(query: &str) -> Vec<String> {
+        query
+            .split_whitespace()
+            .map(|term| {
+                term.chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .collect::<String>()
+                    .to_lowercase()
+            })
+            .filter(|term| term.chars().count() >= MIN_TERM_LENGTH)
+            .collect()
+    }
+
+    // Termos já limpos de operadores, com curinga de prefixo para o BOOLEAN MODE
+    pub fn boolean_query(terms: &[String]) -> String {
+        terms
+            .iter()
+            .map(|term| format!("{}*", term))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // Entidades que o renderizador e o sanitizador emitem no texto; o &amp; vem por
+    // último para não decodificar duas vezes algo como "&amp;lt;"
+    const ENTITIES: [(&str, &str); 6] = [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&nbsp;", " "),
+        ("&amp;", "&"),
+    ];
+
+    /// Texto puro de um trecho HTML, como o `content_html` renderizado.
+    pub fn strip_tags(text: &str) -> String {
+        let mut plain = String::with_capacity(text.len());
+        let mut inside_tag = false;
+
+        for c in text.chars() {
+            match c {
+                '<' => inside_tag = true,
+                '>' if inside_tag => {
+                    inside_tag = false;
+                    plain.push(' ');
+                }
+                _ if !inside_tag => plain.push(c),
+                _ => {}
+            }
+        }
+
+        let plain = Self::ENTITIES
+            .iter()
+            .fold(plain, |plain, (entity, c)| plain.replace(entity, c));
+        plain.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    // Comparação caractere a caractere para manter os índices alinhados com o texto original
+    fn matches_at(lowered: &[char], index: usize, term: &[char]) -> bool {
+        lowered.len() >= index + term.len() && lowered[index..index + term.len()] == *term
+    }
+
+    /// Trecho do texto ao redor da primeira ocorrência, com os termos envoltos em `<mark>`.
+    pub fn highlight(text: &str, terms: &[String], radius: usize) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let lowered: Vec<char> = chars
+            .iter()
+            .map(|c| c.to_lowercase().next().unwrap_or(*c))
+            .collect();
+        let terms: Vec<Vec<char>> = terms.iter().map(|term| term.chars().collect()).collect();
+
+        let first_match = (0..lowered.len()).find(|&index| {
+            terms
+                .iter()
+                .any(|term| Self::matches_at(&lowered, index, term))
+        });
+
+        let (start, end) = match first_match {
+            Some(index) => (
+                index.saturating_sub(radius),
+                (index + radius).min(chars.len()),
+            ),
+            None => (0, (radius * 2).min(chars.len())),
+        };
+
+        let mut snippet = String::new();
+        if start > 0 {
+            snippet.push('…');
+        }
+
+        let mut index = start;
+        while index < end {
+            let matched = terms
+                .iter()
+                .filter(|term| Self::matches_at(&lowered, index, term))
+                .map(|term| term.len())
+                .max();
+
+            match matched {
+                Some(len) => {
+                    let word: String = chars[index..index + len].iter().collect();
+                    snippet.push_str(&format!("<mark>{}</mark>", HelpersXml::escape(&word)));
+                    index += len;
+                }
+                None => {
+                    snippet.push_str(&HelpersXml::escape(&chars[index].to_string()));
+                    index += 1;
+                }
+            }
+        }
+
+        if end < chars.len() {
+            snippet.push('…');
+        }
+        snippet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms_drop_operators_and_short_words() {
+        assert_eq!(
+            ServicesPostSearch::terms("Rust +async -de \"tokio\" é io"),
+            vec!["rust", "async", "tokio"]
+        );
+    }
+
+    #[test]
+    fn terms_are_empty_without_indexable_words() {
+        assert!(ServicesPostSearch::terms("a de +- ").is_empty());
+    }
+
+    #[test]
+    fn strip_tags_turns_rendered_html_into_text() {
+        assert_eq!(
+            ServicesPostSearch::strip_tags(
+                "<h2 id=\"x\">Tokio</h2><p>Use <code>Vec&lt;T&gt;</code> &amp;amp; mais</p>"
+            ),
+            "Tokio Use Vec<T> &amp; mais"
+        );
+    }
+
+    #[test]
+    fn highlight_marks_terms_case_insensitively() {
+        let terms = ServicesPostSearch::terms("rust");
+
+        assert_eq!(
+            ServicesPostSearch::highlight("Aprenda Rust hoje", &terms, 50),
+            "Aprenda <mark>Rust</mark> hoje"
+        );
+    }
+
+    #[test]
+    fn highlight_trims_around_first_match_and_escapes_html() {
+        let terms = ServicesPostSearch::terms("tokio");
+        let text = "0123456789 <b>tokio</b> 0123456789";
+
+        assert_eq!(
+            ServicesPostSearch::highlight(text, &terms, 5),
+            "…9 &lt;b&gt;<mark>tokio</mark>…"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use crate::mvc::models::post::model_post::ModelPost;
+use once_cell::sync::OnceCell;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use tantivy::{
+    collector::{Count, FacetCollector, TopDocs},
+    doc,
+    query::{BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query, TermQuery},
+    schema::{
+        Facet, FacetOptions, Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions,
+        Value as _, FAST, INDEXED, STORED,
+    },
+    tokenizer::{
+        AsciiFoldingFilter, Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer,
+        TextAnalyzer,
+    },
+    Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term,
+};
+
+static SEARCH_INDEX: OnceCell<ServicesPostSearchIndex> = OnceCell::new();
+
+const TOKENIZER: &str = "pt_stem";
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+
+struct SearchFields {
+    id: Field,
+    title: Field,
+    description: Field,
+    content: Field,
+    slug: Field,
+    author_name: Field,
+    category_name: Field,
+    category: Field,
+    author: Field,
+}
+
+pub struct SearchIndexQuery<'a> {
+    pub text: &'a str,
+    pub category_id: Option<i32>,
+    pub author_id: Option<i32>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+pub struct ServicesPostSearchIndex {
+    index: Index,
+    reader: IndexReader,
+    writer: Mutex<IndexWriter>,
+    fields: SearchFields,
+}
+
+fn category_facet(category_id: i64) -> Facet {
+    Facet::from_path(["category".to_string(), category_id.to_string()])
+}
+
+fn author_facet(author_id: i64) -> Facet {
+    Facet::from_path(["author".to_string(), author_id.to_string()])
+}
+
+impl ServicesPostSearchIndex {
+    pub fn init() -> tantivy::Result<()> {
+        let text_options = |stored: bool| {
+            let options = TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(TOKENIZER)
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            );
+            if stored {
+                options.set_stored()
+            } else {
+                options
+            }
+        };
+
+        let mut schema_builder = Schema::builder();
+        let fields = SearchFields {
+            id: schema_builder.add_u64_field("id", INDEXED | STORED | FAST),
+            title: schema_builder.add_text_field("title", text_options(true)),
+            description: schema_builder.add_text_field("description", text_options(true)),
+            content: schema_builder.add_text_field("content", text_options(false)),
+            slug: schema_builder.add_text_field("slug", STORED),
+            author_name: schema_builder.add_text_field("author_name", STORED),
+            category_name: schema_builder.add_text_field("category_name", STORED),
+            category: schema_builder.add_facet_field("category", FacetOptions::default()),
+            author: schema_builder.add_facet_field("author", FacetOptions::default()),
+        };
+
+        let index = Index::create_in_ram(schema_builder.build());
+
+        // Acentos removidos antes do stemmer para que "programação" e "programacao" coincidam
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit(40))
+            .filter(LowerCaser)
+            .filter(AsciiFoldingFilter)
+            .filter(Stemmer::new(Language::Portuguese))
+            .build();
+        index.tokenizers().register(TOKENIZER, analyzer);
+
+        let writer = index.writer(WRITER_MEMORY_BYTES)?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+
+        SEARCH_INDEX
+            .set(Self {
+                index,
+                reader,
+                writer: Mutex::new(writer),
+                fields,
+            })
+            .map_err(|_| tantivy::TantivyError::InternalError("Índice já iniciado".into()))?;
+        Ok(())
+    }
+
+    pub fn get_instance() -> Option<&'static ServicesPostSearchIndex> {
+        SEARCH_INDEX.get()
+    }
+
+    fn document(&self, post: &Value) -> TantivyDocument {
+        let fields = &self.fields;
+        let text = |field: &str| post[field].as_str().unwrap_or_default().to_string();
+
+        doc!(
+            fields.id => post["id"].as_u64().unwrap_or_default(),
+            fields.title => text("title"),
+            fields.description => text("description"),
+            fields.content => text("content"),
+            fields.slug => text("slug"),
+            fields.author_name => text("author_name"),
+            fields.category_name => text("category_name"),
+            fields.category => category_facet(post["category_id"].as_i64().unwrap_or_default()),
+            fields.author => author_facet(post["author_id"].as_i64().unwrap_or_default()),
+        )
+    }
+
+    fn write<F>(&self, change: F) -> tantivy::Result<()>
+    where
+        F: FnOnce(&mut IndexWriter, &SearchFields) -> tantivy::Result<()>,
+    {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| tantivy::TantivyError::InternalError("Writer envenenado".into()))?;
+        change(&mut writer, &self.fields)?;
+        writer.commit()?;
+        self.reader.reload()
+    }
+
+    pub async fn rebuild() -> Result<usize, String> {
+        let instance = Self::get_instance().ok_or("Índice de busca não iniciado")?;
+        let posts = ModelPost::select_indexable_posts(None)
+            .await
+            .map_err(|e| e.to_string())?;
+        let total = posts.len();
+
+        tokio::task::spawn_blocking(move || {
+            let documents: Vec<TantivyDocument> =
+                posts.iter().map(|post| instance.document(post)).collect();
+
+            instance.write(|writer, _| {
+                writer.delete_all_documents()?;
+                for document in documents {
+                    writer.add_document(document)?;
+                }
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+        Ok(total)
+    }
+
+    /// Reindexa um post em segundo plano; posts fora do ar apenas saem do índice.
+    pub fn sync_post(post_id: i32) {
+        let Some(instance) = Self::get_instance() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let posts = match ModelPost::select_indexable_posts(Some(post_id)).await {
+                Ok(posts) => posts,
+                Err(e) => {
+                    eprintln!("Erro ao buscar post {} para o índice: {}", post_id, e);
+                    return;
+                }
+            };
+
+            let result = tokio::task::spawn_blocking(move || {
+                let documents: Vec<TantivyDocument> =
+                    posts.iter().map(|post| instance.document(post)).collect();
+
+                instance.write(|writer, fields| {
+                    writer.delete_term(Term::from_field_u64(fields.id, post_id as u64));
+                    for document in documents {
+                        writer.add_document(document)?;
+                    }
+                    Ok(())
+                })
+            })
+            .await;
+
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("Erro ao indexar post {}: {}", post_id, e),
+                Err(e) => eprintln!("Erro ao indexar post {}: {}", post_id, e),
+            }
+        });
+    }
+
+    pub fn schedule_rebuild() {
+        tokio::spawn(async {
+            if let Err(e) = Self::rebuild().await {
+                eprintln!("Erro ao reconstruir índice de busca: {}", e);
+            }
+        });
+    }
+
+    fn fuzzy_distance(token: &str) -> u8 {
+        match token.chars().count() {
+            0..=4 => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn search(&self, search: &SearchIndexQuery) -> tantivy::Result<Value> {
+        let fields = &self.fields;
+        let mut analyzer = self.index.tokenizer_for_field(fields.title)?;
+        let mut tokens: Vec<String> = vec![];
+        analyzer
+            .token_stream(search.text)
+            .process(&mut |token| tokens.push(token.text.clone()));
+
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = tokens
+            .iter()
+            .map(|token| {
+                let distance = Self::fuzzy_distance(token);
+                let fuzzy = |field: Field| {
+                    FuzzyTermQuery::new(Term::from_field_text(field, token), distance, true)
+                };
+
+                let per_field: Vec<(Occur, Box<dyn Query>)> = vec![
+                    (
+                        Occur::Should,
+                        Box::new(BoostQuery::new(Box::new(fuzzy(fields.title)), 2.0)),
+                    ),
+                    (Occur::Should, Box::new(fuzzy(fields.description))),
+                    (Occur::Should, Box::new(fuzzy(fields.content))),
+                ];
+                (
+                    Occur::Must,
+                    Box::new(BooleanQuery::new(per_field)) as Box<dyn Query>,
+                )
+            })
+            .collect();
+
+        if let Some(category_id) = search.category_id {
+            let term = Term::from_facet(fields.category, &category_facet(category_id as i64));
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+
+        if let Some(author_id) = search.author_id {
+            let term = Term::from_facet(fields.author, &author_facet(author_id as i64));
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+
+        let query = BooleanQuery::new(clauses);
+
+        let mut category_collector = FacetCollector::for_field("category");
+        category_collector.add_facet("/category");
+        let mut author_collector = FacetCollector::for_field("author");
+        author_collector.add_facet("/author");
+
+        let searcher = self.reader.searcher();
+        let (top_docs, total, category_counts, author_counts) = searcher.search(
+            &query,
+            &(
+                TopDocs::with_limit(search.limit).and_offset(search.offset),
+                Count,
+                category_collector,
+                author_collector,
+            ),
+        )?;
+
+        let mut hits = vec![];
+        for (score, address) in top_docs {
+            let document: TantivyDocument = searcher.doc(address)?;
+            let text = |field: Field| {
+                document
+                    .get_first(field)
+                    .and_then(|value| value.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+
+            hits.push(json!({
+                "id": document.get_first(fields.id).and_then(|value| value.as_u64()),
+                "title": text(fields.title),
+                "description": text(fields.description),
+                "slug": text(fields.slug),
+                "author_name": text(fields.author_name),
+                "category_name": text(fields.category_name),
+                "score": score,
+            }));
+        }
+
+        let facet_counts = |counts: &tantivy::collector::FacetCounts, root: &str| {
+            counts
+                .get(root)
+                .map(|(facet, count)| {
+                    json!({
+                        "id": facet.to_path().last().and_then(|id| id.parse::<i32>().ok()),
+                        "count": count,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        Ok(json!({
+            "hits": hits,
+            "total": total,
+            "facets": {
+                "categories": facet_counts(&category_counts, "/category"),
+                "authors": facet_counts(&author_counts, "/author"),
+            }
+        }))
+    }
+}
@@ -0,0 +1,72 @@
+use crate::helpers::xml::helpers_xml::HelpersXml;
+use chrono::{DateTime, Utc};
+pub struct ServicesPostSitemap;
+
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: DateTime<Utc>,
+}
+
+impl ServicesPostSitemap {
+    // Limite de URLs por arquivo definido pelo protocolo sitemaps.org
+    pub const MAX_URLS_PER_SITEMAP: usize = 50_000;
+
+    pub fn urlset(urls: &[SitemapUrl]) -> String {
+        let entries: String = urls
+            .iter()
+            .map(|url| {
+                format!(
+                    "<url><loc>{}</loc><lastmod>{}</lastmod></url>",
+                    HelpersXml::escape(&url.loc),
+                    url.lastmod.format("%Y-%m-%d")
+                )
+            })
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">{}</urlset>",
+            entries
+        )
+    }
+
+    pub fn index(api_url: &str, urls: &[SitemapUrl]) -> String {
+        let entries: String = urls
+            .chunks(Self::MAX_URLS_PER_SITEMAP)
+            .enumerate()
+            .map(|(page, chunk)| {
+                let lastmod = chunk
+                    .iter()
+                    .map(|url| url.lastmod)
+                    .max()
+                    .unwrap_or_else(Utc::now);
+                format!(
+                    "<sitemap><loc>{}/sitemap/{}</loc><lastmod>{}</lastmod></sitemap>",
+                    HelpersXml::escape(api_url.trim_end_matches('/')),
+                    page + 1,
+                    lastmod.format("%Y-%m-%d")
+                )
+            })
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">{}</sitemapindex>",
+            entries
+        )
+    }
+
+    pub fn robots(user_agent: &str, disallow: &[String], sitemap_url: &str) -> String {
+        let mut lines = vec![format!("User-agent: {}", user_agent)];
+
+        if disallow.is_empty() {
+            lines.push("Disallow:".to_string());
+        } else {
+            lines.extend(disallow.iter().map(|path| format!("Disallow: {}", path)));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("Sitemap: {}", sitemap_url));
+        lines.join("\n") + "\n"
+    }
+}
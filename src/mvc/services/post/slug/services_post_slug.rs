@@ -0,0 +1,91 @@
+use deunicode::deunicode;
+
+pub struct ServicesPostSlug;
+
+impl ServicesPostSlug {
+    pub const MAX_LENGTH: usize = 80;
+
+    // Slugs que colidem com rotas do blog
+    const RESERVED: [&'static str; 16] = [
+        "admin", "api", "atom", "author", "category", "create", "edit", "feed", "manage", "new",
+        "popular", "remove", "search", "sitemap", "tags", "trash",
+    ];
+
+    /// Translitera para ASCII, une separadores repetidos e corta no limite de palavra.
+    pub fn slugify(text: &str) -> String {
+        let mut slug = String::new();
+        for c in deunicode(text).to_lowercase().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let mut slug = slug.trim_end_matches('-').to_string();
+
+        if slug.len() > Self::MAX_LENGTH {
+            let cut = match slug[..=Self::MAX_LENGTH].rfind('-') {
+                Some(index) if index > 0 => index,
+                _ => Self::MAX_LENGTH,
+            };
+            slug.truncate(cut);
+        }
+
+        if slug.is_empty() {
+            return "post".to_string();
+        }
+
+        if Self::RESERVED.contains(&slug.as_str()) {
+            slug.push_str("-post");
+        }
+
+        slug
+    }
+
+    /// Primeiro slug livre entre `base`, `base-2`, `base-3`...
+    pub fn unique(base: &str, taken: &[String]) -> String {
+        if !taken.iter().any(|slug| slug == base) {
+            return base.to_string();
+        }
+
+        (2..)
+            .map(|counter| format!("{}-{}", base, counter))
+            .find(|candidate| !taken.contains(candidate))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_transliterates_and_collapses_separators() {
+        assert_eq!(
+            ServicesPostSlug::slugify("  Olá, Mundo! -- Ação & Reação  "),
+            "ola-mundo-acao-reacao"
+        );
+    }
+
+    #[test]
+    fn slugify_cuts_long_titles_at_a_word_boundary() {
+        let slug = ServicesPostSlug::slugify(&"palavra ".repeat(20));
+
+        assert!(slug.len() <= ServicesPostSlug::MAX_LENGTH);
+        assert!(slug.ends_with("palavra"));
+    }
+
+    #[test]
+    fn slugify_avoids_empty_and_reserved_slugs() {
+        assert_eq!(ServicesPostSlug::slugify("!!!"), "post");
+        assert_eq!(ServicesPostSlug::slugify("Search"), "search-post");
+    }
+
+    #[test]
+    fn unique_appends_the_first_free_counter() {
+        let taken = vec!["rust".to_string(), "rust-2".to_string()];
+
+        assert_eq!(ServicesPostSlug::unique("rust", &taken), "rust-3");
+        assert_eq!(ServicesPostSlug::unique("tokio", &taken), "tokio");
+    }
+}
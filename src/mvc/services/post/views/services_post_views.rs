@@ -0,0 +1,148 @@
+use crate::mvc::models::post::model_post::ModelPost;
+use dotenv::dotenv;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const BOT_MARKERS: [&str; 14] = [
+    "bot",
+    "crawl",
+    "spider",
+    "slurp",
+    "curl",
+    "wget",
+    "python",
+    "headless",
+    "preview",
+    "monitor",
+    "scrapy",
+    "httpclient",
+    "facebookexternalhit",
+    "lighthouse",
+];
+
+#[derive(Default)]
+struct ViewBuffer {
+    pending: HashMap<i32, u32>,
+    // Última visualização contada de cada visitante em cada post
+    seen: HashMap<(i32, String), Instant>,
+}
+
+static BUFFER: Lazy<Mutex<ViewBuffer>> = Lazy::new(|| Mutex::new(ViewBuffer::default()));
+
+pub struct ServicesPostViews;
+
+impl ServicesPostViews {
+    fn env_secs(name: &str, default: u64) -> Duration {
+        dotenv().ok();
+        let secs = std::env::var(name)
+            .ok()
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .unwrap_or(default);
+        Duration::from_secs(secs)
+    }
+
+    fn dedup_window() -> Duration {
+        Self::env_secs("POST_VIEW_DEDUP_SECS", 30 * 60)
+    }
+
+    pub fn is_bot(user_agent: &str) -> bool {
+        let user_agent = user_agent.to_lowercase();
+        user_agent.trim().is_empty() || BOT_MARKERS.iter().any(|marker| user_agent.contains(marker))
+    }
+
+    /// Conta a visualização em memória, ignorando robôs e repetições dentro da janela.
+    pub fn record(post_id: i32, visitor: &str, user_agent: &str) -> bool {
+        if Self::is_bot(user_agent) {
+            return false;
+        }
+
+        let window = Self::dedup_window();
+        let key = (post_id, format!("{}|{}", visitor, user_agent));
+        let now = Instant::now();
+
+        let mut buffer = BUFFER.lock().unwrap();
+        if let Some(last) = buffer.seen.get(&key) {
+            if now.duration_since(*last) < window {
+                return false;
+            }
+        }
+
+        buffer.seen.insert(key, now);
+        *buffer.pending.entry(post_id).or_insert(0) += 1;
+        true
+    }
+
+    /// Visualizações ainda não gravadas no banco.
+    pub fn pending(post_id: i32) -> u32 {
+        BUFFER
+            .lock()
+            .unwrap()
+            .pending
+            .get(&post_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub async fn flush() -> Result<usize, sqlx::Error> {
+        let pending: Vec<(i32, u32)> = {
+            let window = Self::dedup_window();
+            let mut buffer = BUFFER.lock().unwrap();
+            buffer.seen.retain(|_, last| last.elapsed() < window);
+            buffer.pending.drain().collect()
+        };
+
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(e) = ModelPost::add_post_views(&pending).await {
+            // Devolve ao buffer para tentar de novo no próximo ciclo
+            let mut buffer = BUFFER.lock().unwrap();
+            for (post_id, views) in pending {
+                *buffer.pending.entry(post_id).or_insert(0) += views;
+            }
+            return Err(e);
+        }
+
+        Ok(pending.len())
+    }
+
+    pub fn spawn() {
+        // tokio::time::interval entra em pânico com período zero
+        let flush_every = Self::env_secs("POST_VIEW_FLUSH_SECS", 60).max(Duration::from_secs(1));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(flush_every);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = Self::flush().await {
+                    eprintln!("Erro ao gravar visualizações: {}", e);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_bot_detects_crawlers_and_missing_user_agents() {
+        assert!(ServicesPostViews::is_bot(
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+        ));
+        assert!(ServicesPostViews::is_bot("   "));
+    }
+
+    #[test]
+    fn is_bot_accepts_regular_browsers() {
+        assert!(!ServicesPostViews::is_bot(
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36"
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 use axum::Router;
 
 use crate::mvc::routes::comment::route_comment;
+use crate::mvc::routes::feed::route_feed;
 use crate::mvc::routes::post::route_post;
+use crate::mvc::routes::sitemap::route_sitemap;
 use crate::mvc::routes::user::route_user;
 
 pub async fn create_app() -> Router {
@@ -9,4 +11,6 @@ pub async fn create_app() -> Router {
         .nest("/user", route_user::create_routes())
         .nest("/post", route_post::create_routes())
         .nest("/comments", route_comment::create_routes())
+        .merge(route_feed::create_routes())
+        .merge(route_sitemap::create_routes())
 }